@@ -0,0 +1,9 @@
+use advent_of_code_2022::puzzles::get_all_puzzles;
+use criterion::{criterion_group, criterion_main, Criterion};
+
+fn puzzles(c: &mut Criterion) {
+    aoc_core::bench::bench_puzzles(c, &get_all_puzzles());
+}
+
+criterion_group!(benches, puzzles);
+criterion_main!(benches);
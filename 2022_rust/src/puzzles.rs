@@ -17,52 +17,33 @@ mod day_16;
 mod day_17;
 mod day_18;
 
-#[derive(Debug, PartialEq)]
-pub enum Result {
-    Number(u32),
-    String(String),
-}
-
-fn panic_not_implemented(_: String) -> Result {
-    panic!("Not implemented")
-}
+use aoc_core::solution::Puzzles;
 
-type Puzzle = (fn(String) -> Result, fn(String) -> Result);
-
-pub fn get_all_puzzles() -> [Puzzle; 25] {
-    [
-        (day_01::solve_first, day_01::solve_second),
-        (day_02::solve_first, day_02::solve_second),
-        (day_03::solve_first, day_03::solve_second),
-        (day_04::solve_first, day_04::solve_second),
-        (day_05::solve_first, day_05::solve_second),
-        (day_06::solve_first, day_06::solve_second),
-        (day_07::solve_first, day_07::solve_second),
-        (day_08::solve_first, day_08::solve_second),
-        (day_09::solve_first, day_09::solve_second),
-        (day_10::solve_first, day_10::solve_second),
-        (day_11::solve_first, day_11::solve_second),
-        (day_12::solve_first, day_12::solve_second),
-        (day_13::solve_first, day_13::solve_second),
-        (day_14::solve_first, day_14::solve_second),
-        (day_15::solve_first, day_15::solve_second),
-        (day_16::solve_first, day_16::solve_second),
-        (day_17::solve_first, day_17::solve_second),
-        (day_18::solve_first, day_18::solve_second),
-        (panic_not_implemented, panic_not_implemented),
-        (panic_not_implemented, panic_not_implemented),
-        (panic_not_implemented, panic_not_implemented),
-        (panic_not_implemented, panic_not_implemented),
-        (panic_not_implemented, panic_not_implemented),
-        (panic_not_implemented, panic_not_implemented),
-        (panic_not_implemented, panic_not_implemented),
+pub fn get_all_puzzles() -> Puzzles {
+    aoc_core::puzzles![
+        2022;
+        day_01::Day01,
+        day_02::Day02,
+        day_03::Day03,
+        day_04::Day04,
+        day_05::Day05,
+        day_06::Day06,
+        day_07::Day07,
+        day_08::Day08,
+        day_09::Day09,
+        day_10::Day10,
+        day_11::Day11,
+        day_12::Day12,
+        day_13::Day13,
+        day_14::Day14,
+        day_15::Day15,
+        day_16::Day16,
+        day_17::Day17,
+        day_18::Day18,
     ]
 }
 
-pub fn assert_eq_number(expected: u32, actual: Result) {
-    assert_eq!(Result::Number(expected), actual)
-}
-
-pub fn assert_eq_string(expected: String, actual: Result) {
-    assert_eq!(Result::String(expected), actual)
+#[cfg(test)]
+mod examples {
+    include!(concat!(env!("OUT_DIR"), "/examples.rs"));
 }
@@ -1,28 +1,38 @@
-use super::Result;
+use aoc_core::answer::Answer;
+use aoc_core::parse::{self, ParseError};
+use aoc_core::solution::Solution;
 use std::collections::BinaryHeap;
 
-pub fn solve_first(input: String) -> Result {
-    Result::Number(solve(input, 1))
-}
-
-pub fn solve_second(input: String) -> Result {
-    Result::Number(solve(input, 3))
-}
+pub struct Day01;
+
+impl Solution for Day01 {
+    const DAY: u8 = 1;
+    const TITLE: &'static str = "Calorie Counting";
+    type Input = Vec<u32>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        input
+            .trim_end()
+            .split("\n\n")
+            .map(|elf| {
+                elf.lines()
+                    .map(|line| parse::number::<u32>(input, line.trim()))
+                    .sum()
+            })
+            .collect()
+    }
 
-fn solve(input: String, top_n: u32) -> u32 {
-    let mut calory_heap: BinaryHeap<u32> = BinaryHeap::new();
+    fn part1(input: &Self::Input) -> Answer {
+        Answer::from(solve(input, 1))
+    }
 
-    let mut current_calories = 0;
-    for line in input.split('\n') {
-        let trimmed_line = line.trim();
-        if trimmed_line.is_empty() {
-            calory_heap.push(current_calories);
-            current_calories = 0;
-        } else {
-            let calory: u32 = trimmed_line.parse().expect("Not a number");
-            current_calories += calory;
-        }
+    fn part2(input: &Self::Input) -> Answer {
+        Answer::from(solve(input, 3))
     }
+}
+
+fn solve(elf_calories: &[u32], top_n: u32) -> u32 {
+    let mut calory_heap: BinaryHeap<u32> = elf_calories.iter().copied().collect();
 
     let mut most_calories_sum = 0;
     for _ in 1..=top_n {
@@ -33,32 +43,13 @@ fn solve(input: String, top_n: u32) -> u32 {
 
 #[cfg(test)]
 mod tests {
-    use crate::puzzles::assert_eq_number;
-
     use super::*;
 
-    static RAW_INPUT: &str = "1000\n\
-    2000\n\
-    3000\n\
-    \n\
-    4000\n\
-    \n\
-    5000\n\
-    6000\n\
-    \n\
-    7000\n\
-    8000\n\
-    9000\n\
-    \n\
-    10000\n";
-
-    #[test]
-    fn solves_first() {
-        assert_eq_number(24000, solve_first(String::from(RAW_INPUT)));
-    }
-
     #[test]
-    fn solves_second() {
-        assert_eq_number(45000, solve_second(String::from(RAW_INPUT)));
+    fn parses_input() {
+        assert_eq!(
+            vec![6000, 4000, 11000, 24000, 10000],
+            Day01::parse(include_str!("../../resources/day_01_input_test.txt")).unwrap()
+        );
     }
 }
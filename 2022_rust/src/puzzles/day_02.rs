@@ -1,6 +1,8 @@
 use phf::phf_map;
 
-use super::Result;
+use aoc_core::answer::Answer;
+use aoc_core::parse::ParseError;
+use aoc_core::solution::Solution;
 
 static SCORE_SHEET_1: phf::Map<char, phf::Map<char, u32>> = phf_map! {
     'A' => phf_map! {
@@ -38,50 +40,46 @@ static SCORE_SHEET_2: phf::Map<char, phf::Map<char, u32>> = phf_map! {
     },
 };
 
-pub fn solve_first(input: String) -> Result {
-    Result::Number(solve(input, &SCORE_SHEET_1))
-}
+pub struct Day02;
 
-pub fn solve_second(input: String) -> Result {
-    Result::Number(solve(input, &SCORE_SHEET_2))
-}
+impl Solution for Day02 {
+    const DAY: u8 = 2;
+    const TITLE: &'static str = "Rock Paper Scissors";
+    type Input = Vec<(char, char)>;
 
-fn solve(input: String, score_sheet: &phf::Map<char, phf::Map<char, u32>>) -> u32 {
-    let mut score = 0;
-    for line in input.trim_end().split('\n') {
-        let mut line = line.chars();
-        let opponent = line
-            .next()
-            .expect("The expected line length is 3 characters");
-        line.next(); // skip space
-        let player: char = line
-            .next()
-            .expect("The expected line length is 3 characters");
-        match score_sheet.get(&opponent).and_then(|m| m.get(&player)) {
-            Some(current_score) => score += current_score,
-            None => panic!("Unknown character"),
-        }
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        input
+            .trim_end()
+            .split('\n')
+            .map(|line| {
+                let mut chars = line.chars();
+                match (chars.next(), chars.next(), chars.next(), chars.next()) {
+                    (Some(opponent @ 'A'..='C'), Some(' '), Some(player @ 'X'..='Z'), None) => {
+                        Ok((opponent, player))
+                    }
+                    _ => Err(ParseError::at(
+                        input,
+                        line,
+                        "Expected opponent A-C and player X-Z separated by a space",
+                    )),
+                }
+            })
+            .collect()
     }
-    score
-}
-
-#[cfg(test)]
-mod tests {
-    use crate::puzzles::assert_eq_number;
 
-    use super::*;
-
-    static RAW_INPUT: &str = "A Y\n\
-    B X\n\
-    C Z\n";
+    fn part1(input: &Self::Input) -> Answer {
+        Answer::from(solve(input, &SCORE_SHEET_1))
+    }
 
-    #[test]
-    fn solves_first() {
-        assert_eq_number(15, solve_first(String::from(RAW_INPUT)))
+    fn part2(input: &Self::Input) -> Answer {
+        Answer::from(solve(input, &SCORE_SHEET_2))
     }
+}
 
-    #[test]
-    fn solves_second() {
-        assert_eq_number(12, solve_second(String::from(RAW_INPUT)))
+fn solve(rounds: &[(char, char)], score_sheet: &phf::Map<char, phf::Map<char, u32>>) -> u32 {
+    let mut score = 0;
+    for (opponent, player) in rounds {
+        score += score_sheet[opponent][player];
     }
+    score
 }
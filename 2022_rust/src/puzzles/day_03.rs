@@ -1,79 +1,81 @@
 use std::collections::HashSet;
 
-use super::Result;
+use itertools::Itertools;
 
-pub fn solve_first(input: String) -> Result {
-    let res = input
-        .trim_end()
-        .split('\n')
-        .map(|line| line.split_at(line.len() / 2))
-        .map(|(first_compartment, second_compartment)| {
-            (
-                first_compartment.chars().collect::<HashSet<char>>(),
-                second_compartment.chars().collect::<HashSet<char>>(),
-            )
-        })
-        .flat_map(|(first_compartment, second_compartment)| {
-            first_compartment
-                .intersection(&second_compartment)
-                .copied()
-                .collect::<Vec<char>>()
-        })
-        .map(item_priority)
-        .sum();
+use aoc_core::answer::Answer;
+use aoc_core::parse::ParseError;
+use aoc_core::solution::Solution;
 
-    Result::Number(res)
-}
-
-pub fn solve_second(input: String) -> Result {
-    let res = input
-        .trim_end()
-        .split('\n')
-        .map(|rucksack| rucksack.chars().collect::<HashSet<char>>())
-        .array_chunks::<3>()
-        .flat_map(|[rucksack1, rucksack2, rucksack3]| {
-            rucksack1
-                .iter()
-                .filter(|i| rucksack2.contains(i))
-                .filter(|i| rucksack3.contains(i))
-                .copied()
-                .collect::<Vec<char>>()
-        })
-        .map(item_priority)
-        .sum();
+pub struct Day03;
 
-    Result::Number(res)
-}
+impl Solution for Day03 {
+    const DAY: u8 = 3;
+    const TITLE: &'static str = "Rucksack Reorganization";
+    type Input = Vec<Vec<u32>>;
 
-fn item_priority(item: char) -> u32 {
-    let item_code = item as u32;
-    match item {
-        'a'..='z' => item_code - ('a' as u32) + 1,
-        'A'..='Z' => item_code - ('A' as u32) + 27,
-        _ => panic!("Unknown item"),
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        input
+            .trim_end()
+            .split('\n')
+            .map(|line| {
+                line.char_indices()
+                    .map(|(i, item)| {
+                        item_priority(item).ok_or_else(|| {
+                            ParseError::at(input, &line[i..i + item.len_utf8()], "Unknown item")
+                        })
+                    })
+                    .collect()
+            })
+            .collect()
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use crate::puzzles::assert_eq_number;
+    fn part1(input: &Self::Input) -> Answer {
+        let res: u32 = input
+            .iter()
+            .map(|rucksack| rucksack.split_at(rucksack.len() / 2))
+            .map(|(first_compartment, second_compartment)| {
+                (
+                    first_compartment.iter().collect::<HashSet<&u32>>(),
+                    second_compartment.iter().collect::<HashSet<&u32>>(),
+                )
+            })
+            .flat_map(|(first_compartment, second_compartment)| {
+                first_compartment
+                    .intersection(&second_compartment)
+                    .copied()
+                    .copied()
+                    .collect::<Vec<u32>>()
+            })
+            .sum();
 
-    use super::*;
+        Answer::from(res)
+    }
 
-    static RAW_INPUT: &str = "vJrwpWtwJgWrhcsFMMfFFhFp\n\
-    jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL\n\
-    PmmdzqPrVvPwwTWBwg\n\
-    wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn\n\
-    ttgJtRGJQctTZtZT\n\
-    CrZsJsPPZsGzwwsLwLmpwMDw\n";
+    fn part2(input: &Self::Input) -> Answer {
+        let res: u32 = input
+            .iter()
+            .map(|rucksack| rucksack.iter().collect::<HashSet<&u32>>())
+            .tuples()
+            .flat_map(|(rucksack1, rucksack2, rucksack3)| {
+                rucksack1
+                    .iter()
+                    .filter(|i| rucksack2.contains(*i))
+                    .filter(|i| rucksack3.contains(*i))
+                    .copied()
+                    .copied()
+                    .collect::<Vec<u32>>()
+            })
+            .sum();
 
-    #[test]
-    fn solves_first() {
-        assert_eq_number(157, solve_first(String::from(RAW_INPUT)));
+        Answer::from(res)
     }
+}
 
-    #[test]
-    fn solves_second() {
-        assert_eq_number(70, solve_second(String::from(RAW_INPUT)));
+fn item_priority(item: char) -> Option<u32> {
+    let item_code = item as u32;
+    match item {
+        'a'..='z' => Some(item_code - ('a' as u32) + 1),
+        'A'..='Z' => Some(item_code - ('A' as u32) + 27),
+        _ => None,
     }
 }
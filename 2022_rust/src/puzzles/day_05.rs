@@ -1,41 +1,57 @@
 use lazy_static::lazy_static;
 use regex::Regex;
 
-use super::Result;
-use crate::utils::unwrap_match_to_usize;
+use crate::utils::{capture_to_number, captures};
+use aoc_core::answer::Answer;
+use aoc_core::parse::ParseError;
+use aoc_core::solution::Solution;
 
 const STACK_STEPPER: usize = 4;
 
 type Stacks = Vec<Vec<u8>>;
 type Operation = (usize, usize, usize);
 
-pub fn solve_first(input: String) -> Result {
-    let (mut stacks, operations) = parse_input(input);
+pub struct Day05;
 
-    for (count, from, to) in operations {
-        for _ in 1..=count {
-            let from_stack = &mut stacks[from];
-            if let Some(item) = from_stack.pop() {
-                let to_stack = &mut stacks[to];
-                to_stack.push(item);
+impl Solution for Day05 {
+    const DAY: u8 = 5;
+    const TITLE: &'static str = "Supply Stacks";
+    type Input = (Stacks, Vec<Operation>);
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        let (stacks, operations) = input;
+        let mut stacks = stacks.clone();
+
+        for &(count, from, to) in operations {
+            for _ in 1..=count {
+                let from_stack = &mut stacks[from];
+                if let Some(item) = from_stack.pop() {
+                    let to_stack = &mut stacks[to];
+                    to_stack.push(item);
+                }
             }
         }
+
+        Answer::from(format_output(stacks))
     }
 
-    Result::String(format_output(stacks))
-}
+    fn part2(input: &Self::Input) -> Answer {
+        let (stacks, operations) = input;
+        let mut stacks = stacks.clone();
 
-pub fn solve_second(input: String) -> Result {
-    let (mut stacks, operations) = parse_input(input);
+        for &(count, from, to) in operations {
+            let from_stack = &mut stacks[from];
+            let mut tmp_stack: Vec<u8> = from_stack.drain((from_stack.len() - count)..).collect();
+            let to_stack = &mut stacks[to];
+            to_stack.append(&mut tmp_stack);
+        }
 
-    for (count, from, to) in operations {
-        let from_stack = &mut stacks[from];
-        let mut tmp_stack: Vec<u8> = from_stack.drain((from_stack.len() - count)..).collect();
-        let to_stack = &mut stacks[to];
-        to_stack.append(&mut tmp_stack);
+        Answer::from(format_output(stacks))
     }
-
-    Result::String(format_output(stacks))
 }
 
 fn format_output(stacks: Stacks) -> String {
@@ -49,12 +65,15 @@ fn format_output(stacks: Stacks) -> String {
     top_of_stacks
 }
 
-fn parse_input(input: String) -> (Stacks, Vec<Operation>) {
+fn parse_input(input: &str) -> Result<(Stacks, Vec<Operation>), ParseError> {
     let mut stacks: Stacks = Vec::new();
     let mut operations: Vec<Operation> = Vec::new();
     let mut parse_stacks = true;
     for line in input.trim_end().split('\n') {
         if parse_stacks {
+            if line.len() < 2 {
+                return Err(ParseError::at(input, line, "Expected a row of crates"));
+            }
             let line = line.as_bytes();
             if b'1' == line[1] {
                 parse_stacks = false;
@@ -73,7 +92,7 @@ fn parse_input(input: String) -> (Stacks, Vec<Operation>) {
                 }
             }
         } else if !line.is_empty() {
-            operations.push(parse_operation(line));
+            operations.push(parse_operation(input, line)?);
         }
     }
 
@@ -81,59 +100,39 @@ fn parse_input(input: String) -> (Stacks, Vec<Operation>) {
         stack.reverse();
     }
 
-    (stacks, operations)
+    Ok((stacks, operations))
 }
 
-fn parse_operation(line: &str) -> Operation {
+fn parse_operation(input: &str, line: &str) -> Result<Operation, ParseError> {
     lazy_static! {
         static ref OPERATION_RE: Regex = Regex::new(r"^move (\d+) from (\d+) to (\d+)$").unwrap();
     }
-    let captures = OPERATION_RE
-        .captures(line)
-        .expect("Input line is not of expected format");
-    (
-        unwrap_match_to_usize(captures.get(1)),
-        unwrap_match_to_usize(captures.get(2)) - 1,
-        unwrap_match_to_usize(captures.get(3)) - 1,
-    )
+    let captures = captures(input, &OPERATION_RE, line)?;
+    let stack_index = |group| {
+        let stack: usize = capture_to_number(input, &captures, group)?;
+        stack
+            .checked_sub(1)
+            .ok_or_else(|| ParseError::at(input, &captures[group], "Stacks are numbered from 1"))
+    };
+    Ok((
+        capture_to_number(input, &captures, 1)?,
+        stack_index(2)?,
+        stack_index(3)?,
+    ))
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::puzzles::assert_eq_string;
-
     use super::*;
 
-    static RAW_INPUT: &str = "    [D]    \n\
-    [N] [C]    \n\
-    [Z] [M] [P]\n 1   2   3 \n\
-    \n\
-    move 1 from 2 to 1\n\
-    move 3 from 1 to 3\n\
-    move 2 from 2 to 1\n\
-    move 1 from 1 to 2\n";
-
     #[test]
     fn parses_input() {
-        let (stacks, operations) = parse_input(String::from(RAW_INPUT));
+        let (stacks, operations) =
+            Day05::parse(include_str!("../../resources/day_05_input_test.txt")).unwrap();
         assert_eq!(
-            vec![
-                vec!['Z' as u8, 'N' as u8],
-                vec!['M' as u8, 'C' as u8, 'D' as u8],
-                vec!['P' as u8]
-            ],
+            vec![vec![b'Z', b'N'], vec![b'M', b'C', b'D'], vec![b'P']],
             stacks
         );
         assert_eq!(vec![(1, 1, 0), (3, 0, 2), (2, 1, 0), (1, 0, 1)], operations);
     }
-
-    #[test]
-    fn solves_first() {
-        assert_eq_string(String::from("CMZ"), solve_first(String::from(RAW_INPUT)));
-    }
-
-    #[test]
-    fn solves_second() {
-        assert_eq_string(String::from("MCD"), solve_second(String::from(RAW_INPUT)));
-    }
 }
@@ -1,60 +1,42 @@
 use std::collections::HashSet;
 
-use super::Result;
+use aoc_core::answer::Answer;
+use aoc_core::parse::ParseError;
+use aoc_core::solution::Solution;
 
-pub fn solve_first(input: String) -> Result {
-    find_distinct(input, 4)
-}
+pub struct Day06;
 
-pub fn solve_second(input: String) -> Result {
-    find_distinct(input, 14)
-}
+impl Solution for Day06 {
+    const DAY: u8 = 6;
+    const TITLE: &'static str = "Tuning Trouble";
+    type Input = Vec<u8>;
 
-fn find_distinct(input: String, size: usize) -> Result {
-    let datastream = input.trim_end().as_bytes();
-    let datastream_length = datastream.len();
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let datastream = input.trim_end();
+        if datastream.len() < 14 {
+            return Err(ParseError::at(input, datastream, "Input is too short"));
+        }
+        Ok(datastream.as_bytes().to_vec())
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        find_distinct(input, 4)
+    }
 
-    if datastream_length < size {
-        panic!("Input is too short");
+    fn part2(input: &Self::Input) -> Answer {
+        find_distinct(input, 14)
     }
+}
+
+fn find_distinct(datastream: &[u8], size: usize) -> Answer {
+    let datastream_length = datastream.len();
 
     for i in 0..(datastream_length - size + 1) {
         let set: HashSet<_> = datastream[i..(i + size)].iter().collect();
         if set.len() == size {
-            return Result::Number((i + size) as u32);
+            return Answer::from(i + size);
         }
     }
 
     panic!("No packet marker found in input");
 }
-
-#[cfg(test)]
-mod tests {
-    use crate::puzzles::assert_eq_number;
-
-    use super::*;
-
-    static RAW_INPUT_1: &str = "mjqjpqmgbljsphdztnvjfqwrcgsmlb\n";
-    static RAW_INPUT_2: &str = "bvwbjplbgvbhsrlpgdmjqwftvncz\n";
-    static RAW_INPUT_3: &str = "nppdvjthqldpwncqszvftbrmjlhg\n";
-    static RAW_INPUT_4: &str = "nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg\n";
-    static RAW_INPUT_5: &str = "zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw\n";
-
-    #[test]
-    fn solves_first() {
-        assert_eq_number(7, solve_first(String::from(RAW_INPUT_1)));
-        assert_eq_number(5, solve_first(String::from(RAW_INPUT_2)));
-        assert_eq_number(6, solve_first(String::from(RAW_INPUT_3)));
-        assert_eq_number(10, solve_first(String::from(RAW_INPUT_4)));
-        assert_eq_number(11, solve_first(String::from(RAW_INPUT_5)));
-    }
-
-    #[test]
-    fn solves_second() {
-        assert_eq_number(19, solve_second(String::from(RAW_INPUT_1)));
-        assert_eq_number(23, solve_second(String::from(RAW_INPUT_2)));
-        assert_eq_number(23, solve_second(String::from(RAW_INPUT_3)));
-        assert_eq_number(29, solve_second(String::from(RAW_INPUT_4)));
-        assert_eq_number(26, solve_second(String::from(RAW_INPUT_5)));
-    }
-}
@@ -3,25 +3,70 @@ use std::collections::HashMap;
 use lazy_static::lazy_static;
 use regex::Regex;
 
-use super::Result;
+use crate::utils::{capture_to_number, captures};
+use aoc_core::answer::Answer;
+use aoc_core::params::{Param, Params};
+use aoc_core::parse::ParseError;
+use aoc_core::solution::Solution;
 
-struct Directory {
+pub struct Directory {
     content: HashMap<String, Directory>,
     size: u32,
 }
 
-pub fn solve_first(input: String) -> Result {
-    let root_directory = parse_input(input);
-    Result::Number(sum_dir_sizes_below(&root_directory, 100_000))
-}
+pub struct Day07;
+
+impl Solution for Day07 {
+    const DAY: u8 = 7;
+    const TITLE: &'static str = "No Space Left On Device";
+    const PARAMS: &'static [Param] = &[
+        Param::new::<u32>(
+            "size_limit",
+            100_000,
+            "Largest size of the directories to sum up",
+        ),
+        Param::new::<u32>("disk_size", 70_000_000, "Total space on the filesystem"),
+        Param::new::<u32>(
+            "required_space",
+            30_000_000,
+            "Unused space needed for the update",
+        ),
+    ];
+    type Input = Directory;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        Self::part1_with(input, &Params::defaults(Self::PARAMS))
+    }
+
+    fn part2(input: &Self::Input) -> Answer {
+        Self::part2_with(input, &Params::defaults(Self::PARAMS))
+    }
 
-pub fn solve_second(input: String) -> Result {
-    let root_directory = parse_input(input);
-    let current_unused_space = 70_000_000 - root_directory.size;
-    let needed_space = 30_000_000 - current_unused_space;
-    find_smallest_above_limit(&root_directory, needed_space)
-        .map(Result::Number)
-        .expect("There isn't any directory that could help with space")
+    fn part1_with(input: &Self::Input, params: &Params) -> Answer {
+        Answer::from(sum_dir_sizes_below(input, params.get("size_limit")))
+    }
+
+    fn part2_with(input: &Self::Input, params: &Params) -> Answer {
+        let disk_size: u32 = params.get("disk_size");
+        let current_unused_space = disk_size.checked_sub(input.size).unwrap_or_else(|| {
+            panic!(
+                "The files take up {} but the disk only holds {disk_size}",
+                input.size
+            )
+        });
+        let needed_space = params
+            .get::<u32>("required_space")
+            .checked_sub(current_unused_space)
+            .filter(|&needed| needed > 0)
+            .expect("There is already enough unused space");
+        find_smallest_above_limit(input, needed_space)
+            .map(Answer::from)
+            .expect("There isn't any directory that could help with space")
+    }
 }
 
 fn find_smallest_above_limit(directory: &Directory, limit: u32) -> Option<u32> {
@@ -43,18 +88,23 @@ fn find_smallest_above_limit(directory: &Directory, limit: u32) -> Option<u32> {
 }
 
 // assuming we cd into every directory only once and ls them only once
-fn parse_input(input: String) -> Directory {
+fn parse_input(input: &str) -> Result<Directory, ParseError> {
     let mut input_lines: Vec<&str> = input.trim_end().split('\n').collect();
     input_lines.reverse();
-    if let Some("$ cd /") = input_lines.pop() {
-        let mut root_directory = create_directory();
+    match input_lines.pop() {
+        Some("$ cd /") => {
+            let mut root_directory = create_directory();
 
-        parse_next_line(&mut root_directory, &mut input_lines);
+            parse_next_line(input, &mut root_directory, &mut input_lines)?;
 
-        return root_directory;
+            Ok(root_directory)
+        }
+        first_line => Err(ParseError::at(
+            input,
+            first_line.unwrap_or_default(),
+            "Input should start with root directory",
+        )),
     }
-
-    panic!("Input should start with root directory")
 }
 
 fn sum_dir_sizes_below(directory: &Directory, limit: u32) -> u32 {
@@ -69,67 +119,69 @@ fn sum_dir_sizes_below(directory: &Directory, limit: u32) -> u32 {
     sum_size
 }
 
-fn parse_next_line(directory: &mut Directory, input_lines: &mut Vec<&str>) -> u32 {
+fn parse_next_line(
+    input: &str,
+    directory: &mut Directory,
+    input_lines: &mut Vec<&str>,
+) -> Result<u32, ParseError> {
     let Directory {
         ref mut content,
         ref mut size,
     } = directory;
 
-    loop {
-        if input_lines.is_empty() {
-            return *size;
-        }
-        let input_line = input_lines.pop().unwrap();
+    while let Some(input_line) = input_lines.pop() {
         let input_line_parts: Vec<&str> = input_line.split(' ').collect();
-        match input_line_parts[1] {
-            "cd" => {
-                let next_directory_name = input_line_parts[2];
-                if ".." == next_directory_name {
-                    return *size;
-                }
-                let next_directory = content
-                    .get_mut(next_directory_name)
-                    .expect("Unknown directory");
-                *size += parse_next_line(next_directory, input_lines);
+        match input_line_parts[..] {
+            ["$", "cd", ".."] => {
+                return Ok(*size);
+            }
+            ["$", "cd", next_directory_name] => {
+                let next_directory = content.get_mut(next_directory_name).ok_or_else(|| {
+                    ParseError::at(input, next_directory_name, "Unknown directory")
+                })?;
+                *size += parse_next_line(input, next_directory, input_lines)?;
+            }
+            ["$", "ls"] => {
+                *size += parse_after_ls(input, content, input_lines)?;
             }
-            "ls" => {
-                *size += parse_after_ls(content, input_lines);
+            _ => {
+                return Err(ParseError::at(
+                    input,
+                    input_line,
+                    "Unexpected command found",
+                ))
             }
-            _ => panic!("Unexpected command found"),
         }
     }
+    Ok(*size)
 }
 
-fn parse_after_ls(content: &mut HashMap<String, Directory>, input_lines: &mut Vec<&str>) -> u32 {
+fn parse_after_ls(
+    input: &str,
+    content: &mut HashMap<String, Directory>,
+    input_lines: &mut Vec<&str>,
+) -> Result<u32, ParseError> {
     lazy_static! {
         static ref LS_OUTPUT_RE: Regex = Regex::new(r"^(\d+|dir) ([a-z\.]+)$").unwrap();
     }
     let mut sum_size = 0;
-    loop {
-        if input_lines.is_empty() {
-            break;
-        }
-        let &input_line = input_lines.last().unwrap();
+    while let Some(&input_line) = input_lines.last() {
         if input_line.starts_with('$') {
             break;
         }
-        let input_line = input_lines.pop().unwrap();
-        let captures = LS_OUTPUT_RE
-            .captures(input_line)
-            .expect("Input line is not of expected format");
-        let dir_or_size = captures.get(1).expect("Match expected").as_str();
-        let name = captures.get(2).expect("Match expected").as_str();
-        match dir_or_size {
+        input_lines.pop();
+        let captures = captures(input, &LS_OUTPUT_RE, input_line)?;
+        match &captures[1] {
             "dir" => {
-                content.insert(String::from(name), create_directory());
+                content.insert(String::from(&captures[2]), create_directory());
             }
-            size => {
-                let size: u32 = size.parse().expect("Expected number here");
+            _ => {
+                let size: u32 = capture_to_number(input, &captures, 1)?;
                 sum_size += size;
             }
         };
     }
-    sum_size
+    Ok(sum_size)
 }
 
 fn create_directory() -> Directory {
@@ -141,41 +193,15 @@ fn create_directory() -> Directory {
 
 #[cfg(test)]
 mod tests {
-    use crate::puzzles::assert_eq_number;
-
     use super::*;
 
-    static RAW_INPUT: &str = "$ cd /\n\
-    $ ls\n\
-    dir a\n\
-    14848514 b.txt\n\
-    8504156 c.dat\n\
-    dir d\n\
-    $ cd a\n\
-    $ ls\n\
-    dir e\n\
-    29116 f\n\
-    2557 g\n\
-    62596 h.lst\n\
-    $ cd e\n\
-    $ ls\n\
-    584 i\n\
-    $ cd ..\n\
-    $ cd ..\n\
-    $ cd d\n\
-    $ ls\n\
-    4060174 j\n\
-    8033020 d.log\n\
-    5626152 d.ext\n\
-    7214296 k\n";
-
-    #[test]
-    fn solves_first() {
-        assert_eq_number(95437, solve_first(String::from(RAW_INPUT)));
-    }
+    const EXAMPLE: &str = include_str!("../../resources/day_07_input_test.txt");
 
     #[test]
-    fn solves_second() {
-        assert_eq_number(24933642, solve_second(String::from(RAW_INPUT)));
+    #[should_panic(expected = "The files take up 48381165 but the disk only holds 10")]
+    fn fails_when_the_files_do_not_fit_on_the_disk() {
+        let mut params = Params::defaults(Day07::PARAMS);
+        params.set("disk_size", 10);
+        Day07::part2_with(&Day07::parse(EXAMPLE).unwrap(), &params);
     }
 }
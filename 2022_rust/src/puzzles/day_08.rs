@@ -1,148 +1,82 @@
-use super::Result;
+use aoc_core::answer::Answer;
+use aoc_core::grid::Grid;
+use aoc_core::parse::ParseError;
+use aoc_core::point::{Direction, Point2};
+use aoc_core::solution::Solution;
 
-pub fn solve_first(input: String) -> Result {
-    let forest: Vec<Vec<u8>> = parse_input_into_forest(input);
-    let forest_width = forest[0].len();
-    let forest_height = forest.len();
-    let mut visibility: Vec<Vec<bool>> = vec![vec![false; forest[0].len()]; forest.len()];
-    for i in 0..forest_height {
-        let mut max_height = 0;
-        for j in 0..forest_width {
-            let tree_height = forest[i][j];
-            if max_height < tree_height {
-                visibility[i][j] = true;
-                max_height = tree_height;
-            }
-            if tree_height == b'9' {
-                break;
-            }
-        }
-    }
-    for i in 0..forest_height {
-        let mut max_height = 0;
-        for j in (0..forest_width).rev() {
-            let tree_height = forest[i][j];
-            if max_height < tree_height {
-                visibility[i][j] = true;
-                max_height = tree_height;
-            }
-            if tree_height == b'9' {
-                break;
-            }
-        }
-    }
-    for j in 0..forest_width {
-        let mut max_height = 0;
-        for i in 0..forest_height {
-            let tree_height = forest[i][j];
-            if max_height < tree_height {
-                visibility[i][j] = true;
-                max_height = tree_height;
-            }
-            if tree_height == b'9' {
-                break;
-            }
-        }
-    }
-    for j in 0..forest_width {
-        let mut max_height = 0;
-        for i in (0..forest_height).rev() {
-            let tree_height = forest[i][j];
-            if max_height < tree_height {
-                visibility[i][j] = true;
-                max_height = tree_height;
-            }
-            if tree_height == b'9' {
-                break;
-            }
-        }
-    }
+pub struct Day08;
 
-    let visible_trees = visibility
-        .iter()
-        .flat_map(|l| l.iter().copied())
-        .filter(|v| *v)
-        .count();
+impl Solution for Day08 {
+    const DAY: u8 = 8;
+    const TITLE: &'static str = "Treetop Tree House";
+    type Input = Grid<u8>;
 
-    Result::Number(visible_trees as u32)
-}
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Grid::parse(input, |c| c.to_digit(10).map(|height| height as u8))
+    }
 
-pub fn solve_second(input: String) -> Result {
-    let forest: Vec<Vec<u8>> = parse_input_into_forest(input);
-    let w = forest[0].len();
-    let h = forest.len();
-    let mut scenic_score = 0;
-    for i in 0..h {
-        for j in 0..w {
-            let cur_scenic_score = get_scenic_score(&forest, (h, w), (i, j));
-            if scenic_score < cur_scenic_score {
-                scenic_score = cur_scenic_score;
+    fn part1(input: &Self::Input) -> Answer {
+        let forest = input;
+        let (width, height) = (forest.width(), forest.height());
+        let mut visibility = Grid::new(width, height, false);
+        // Looking into the forest from every tree on the edge
+        let sight_lines = (0..height)
+            .flat_map(|y| {
+                [
+                    (Point2::new(0, y), Direction::Right),
+                    (Point2::new(width - 1, y), Direction::Left),
+                ]
+            })
+            .chain((0..width).flat_map(|x| {
+                [
+                    (Point2::new(x, 0), Direction::Down),
+                    (Point2::new(x, height - 1), Direction::Up),
+                ]
+            }));
+        for (edge, direction) in sight_lines {
+            visibility[edge] = true;
+            let mut max_height = forest[edge];
+            for position in forest.ray(edge, direction) {
+                if max_height == 9 {
+                    break;
+                }
+                if max_height < forest[position] {
+                    visibility[position] = true;
+                    max_height = forest[position];
+                }
             }
         }
-    }
-    Result::Number(scenic_score)
-}
 
-fn parse_input_into_forest(input: String) -> Vec<Vec<u8>> {
-    input
-        .trim_end()
-        .split('\n')
-        .map(|line| line.as_bytes().to_vec())
-        .collect()
-}
+        let visible_trees = visibility.iter().filter(|(_, visible)| **visible).count();
 
-fn get_scenic_score(forest: &[Vec<u8>], (h, w): (usize, usize), (i, j): (usize, usize)) -> u32 {
-    let tree_height = forest[i][j];
-    let mut score_up = 0;
-    while i > score_up {
-        score_up += 1;
-        if forest[i - score_up][j] >= tree_height {
-            break;
-        }
+        Answer::from(visible_trees)
     }
-    let mut score_left = 0;
-    while j > score_left {
-        score_left += 1;
-        if forest[i][j - score_left] >= tree_height {
-            break;
-        }
-    }
-    let mut score_down = 0;
-    while i + score_down + 1 < h {
-        score_down += 1;
-        if forest[i + score_down][j] >= tree_height {
-            break;
-        }
-    }
-    let mut score_right = 0;
-    while j + score_right + 1 < w {
-        score_right += 1;
-        if forest[i][j + score_right] >= tree_height {
-            break;
-        }
-    }
-    (score_up * score_left * score_down * score_right) as u32
-}
-
-#[cfg(test)]
-mod tests {
-    use crate::puzzles::assert_eq_number;
-
-    use super::*;
 
-    static RAW_INPUT: &str = "30373\n\
-    25512\n\
-    65332\n\
-    33549\n\
-    35390\n";
-
-    #[test]
-    fn solves_first() {
-        assert_eq_number(21, solve_first(String::from(RAW_INPUT)));
+    fn part2(input: &Self::Input) -> Answer {
+        let forest = input;
+        let scenic_score = forest
+            .positions()
+            .map(|position| get_scenic_score(forest, position))
+            .max()
+            .unwrap_or(0);
+        Answer::from(scenic_score)
     }
+}
 
-    #[test]
-    fn solves_second() {
-        assert_eq_number(8, solve_second(String::from(RAW_INPUT)));
-    }
+/// Product of the viewing distances in every direction, up to the edge or the first tree as high
+fn get_scenic_score(forest: &Grid<u8>, position: Point2<usize>) -> u32 {
+    let tree_height = forest[position];
+    Direction::ORTHOGONAL
+        .into_iter()
+        .map(|direction| {
+            let mut distance = 0;
+            for other in forest.ray(position, direction) {
+                distance += 1;
+                if forest[other] >= tree_height {
+                    break;
+                }
+            }
+            distance
+        })
+        .product()
 }
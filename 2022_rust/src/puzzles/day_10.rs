@@ -1,71 +1,82 @@
-use super::Result;
+use aoc_core::answer::{Answer, PixelGrid};
+use aoc_core::parse::{self, ParseError};
+use aoc_core::solution::Solution;
 
 const INTERESTING_SIGNAL_START: i32 = 20;
 const SIGNAL_CYCLE: i32 = 40;
 
-#[derive(Clone, Debug)]
-enum Instruction {
+#[derive(Clone, Copy, Debug)]
+pub enum Instruction {
     Addx(i32),
     Noop,
 }
 
-pub fn solve_first(input: String) -> Result {
-    let mut strengths = 0;
-    let mut to_add = INTERESTING_SIGNAL_START;
-    let mut x = 1;
-    let mut i = 1;
-    for instruction in parse_input(input) {
-        match instruction {
-            Instruction::Noop => {
-                i += 1;
-                check_to_save(i, x, &mut to_add, &mut strengths);
-            }
-            Instruction::Addx(n) => {
-                i += 1;
-                check_to_save(i, x, &mut to_add, &mut strengths);
-                i += 1;
-                x += n;
-                check_to_save(i, x, &mut to_add, &mut strengths);
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: u8 = 10;
+    const TITLE: &'static str = "Cathode-Ray Tube";
+    type Input = Vec<Instruction>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        let mut strengths = 0;
+        let mut to_add = INTERESTING_SIGNAL_START;
+        let mut x = 1;
+        let mut i = 1;
+        for instruction in input {
+            match instruction {
+                Instruction::Noop => {
+                    i += 1;
+                    check_to_save(i, x, &mut to_add, &mut strengths);
+                }
+                Instruction::Addx(n) => {
+                    i += 1;
+                    check_to_save(i, x, &mut to_add, &mut strengths);
+                    i += 1;
+                    x += n;
+                    check_to_save(i, x, &mut to_add, &mut strengths);
+                }
             }
         }
+        Answer::from(strengths)
     }
-    Result::Number(strengths as u32)
-}
 
-pub fn solve_second(input: String) -> Result {
-    let mut crt_screen = String::from("");
-    let mut x = 1;
-    let mut i = 1;
+    fn part2(input: &Self::Input) -> Answer {
+        let mut crt_screen: Vec<Vec<bool>> = Vec::new();
+        let mut x = 1;
+        let mut i = 1;
 
-    for instruction in parse_input(input) {
-        match instruction {
-            Instruction::Noop => {
-                draw_to_crt(i, x, &mut crt_screen);
-                i += 1;
-            }
-            Instruction::Addx(n) => {
-                draw_to_crt(i, x, &mut crt_screen);
-                i += 1;
-                draw_to_crt(i, x, &mut crt_screen);
-                i += 1;
-                x += n;
+        for instruction in input {
+            match instruction {
+                Instruction::Noop => {
+                    draw_to_crt(i, x, &mut crt_screen);
+                    i += 1;
+                }
+                Instruction::Addx(n) => {
+                    draw_to_crt(i, x, &mut crt_screen);
+                    i += 1;
+                    draw_to_crt(i, x, &mut crt_screen);
+                    i += 1;
+                    x += n;
+                }
             }
         }
+        Answer::from(PixelGrid::new(crt_screen))
     }
-    Result::String(crt_screen)
 }
 
-fn draw_to_crt(signal_index: i32, register_x: i32, crt_screen: &mut String) {
+fn draw_to_crt(signal_index: i32, register_x: i32, crt_screen: &mut Vec<Vec<bool>>) {
     let normalized_index = (signal_index - 1) % SIGNAL_CYCLE;
     if normalized_index == 0 {
-        crt_screen.push('\n');
+        crt_screen.push(Vec::new());
+    }
+    if let Some(crt_row) = crt_screen.last_mut() {
+        crt_row.push((register_x - normalized_index).abs() <= 1);
     }
-    let c = if (register_x - normalized_index).abs() <= 1 {
-        '#'
-    } else {
-        '.'
-    };
-    crt_screen.push(c);
 }
 
 fn check_to_save(
@@ -80,46 +91,16 @@ fn check_to_save(
     }
 }
 
-fn parse_input(input: String) -> Vec<Instruction> {
+fn parse_input(input: &str) -> Result<Vec<Instruction>, ParseError> {
     input
         .trim_end()
         .split('\n')
         .map(|line| match line {
-            "noop" => Instruction::Noop,
-            line => {
-                let line: Vec<&str> = line.split(' ').collect();
-                if line[0] != "addx" {
-                    panic!("Unknown instruction")
-                }
-                let number: i32 = line[1].parse().unwrap();
-                Instruction::Addx(number)
-            }
+            "noop" => Ok(Instruction::Noop),
+            line => match line.strip_prefix("addx ") {
+                Some(number) => Ok(Instruction::Addx(parse::number(input, number)?)),
+                None => Err(ParseError::at(input, line, "Unknown instruction")),
+            },
         })
         .collect()
 }
-
-#[cfg(test)]
-mod tests {
-    use crate::input_utils::get_test_input;
-    use crate::puzzles::{assert_eq_number, assert_eq_string};
-
-    use super::*;
-
-    static RAW_OUTPUT: &str = "\n\
-    ##..##..##..##..##..##..##..##..##..##..\n\
-    ###...###...###...###...###...###...###.\n\
-    ####....####....####....####....####....\n\
-    #####.....#####.....#####.....#####.....\n\
-    ######......######......######......####\n\
-    #######.......#######.......#######.....";
-
-    #[test]
-    fn solves_first() {
-        assert_eq_number(13140, solve_first(get_test_input(10)));
-    }
-
-    #[test]
-    fn solves_second() {
-        assert_eq_string(String::from(RAW_OUTPUT), solve_second(get_test_input(10)));
-    }
-}
@@ -1,16 +1,18 @@
 use core::str::FromStr;
 
-use super::Result;
+use aoc_core::answer::Answer;
+use aoc_core::parse::{self, ParseError};
+use aoc_core::solution::Solution;
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 enum Operation {
     Add(u64),
     Multiply(u64),
     Sqr(),
 }
 
-#[derive(Debug)]
-struct Monkey {
+#[derive(Clone, Debug)]
+pub struct Monkey {
     items: Vec<u64>,
     operation: Operation,
     divisible_by: u64,
@@ -19,26 +21,38 @@ struct Monkey {
     inspected: u64,
 }
 
-pub fn solve_first(input: String) -> Result {
-    let mut monkeys = parse_input(input);
-    Result::String(get_monkey_business(&mut monkeys, Box::new(|i| i / 3), 20))
-}
+pub struct Day11;
+
+impl Solution for Day11 {
+    const DAY: u8 = 11;
+    const TITLE: &'static str = "Monkey in the Middle";
+    type Input = Vec<Monkey>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
 
-pub fn solve_second(input: String) -> Result {
-    let mut monkeys = parse_input(input);
-    let simplify_by: u64 = monkeys.iter().map(|m| m.divisible_by).product();
-    Result::String(get_monkey_business(
-        &mut monkeys,
-        Box::new(move |i: u64| -> u64 { i % simplify_by }),
-        10_000,
-    ))
+    fn part1(input: &Self::Input) -> Answer {
+        let mut monkeys = input.clone();
+        Answer::from(get_monkey_business(&mut monkeys, Box::new(|i| i / 3), 20))
+    }
+
+    fn part2(input: &Self::Input) -> Answer {
+        let mut monkeys = input.clone();
+        let simplify_by: u64 = monkeys.iter().map(|m| m.divisible_by).product();
+        Answer::from(get_monkey_business(
+            &mut monkeys,
+            Box::new(move |i: u64| -> u64 { i % simplify_by }),
+            10_000,
+        ))
+    }
 }
 
 fn get_monkey_business(
-    monkeys: &mut Vec<Monkey>,
+    monkeys: &mut [Monkey],
     simplifier: Box<dyn Fn(u64) -> u64>,
     rounds: usize,
-) -> String {
+) -> u64 {
     for _ in 0..rounds {
         for i in 0..monkeys.len() {
             let monkey = &mut monkeys[i];
@@ -52,7 +66,7 @@ fn get_monkey_business(
                         Operation::Sqr() => *item * *item,
                     };
                     let item = simplifier(item);
-                    let to_monkey = if item % monkey.divisible_by == 0 {
+                    let to_monkey = if item.is_multiple_of(monkey.divisible_by) {
                         monkey.on_true
                     } else {
                         monkey.on_false
@@ -69,26 +83,21 @@ fn get_monkey_business(
     }
     monkeys.sort_by_key(|m| m.inspected);
     monkeys.reverse();
-    monkeys
-        .iter()
-        .take(2)
-        .map(|m| m.inspected)
-        .product::<u64>()
-        .to_string()
+    monkeys.iter().take(2).map(|m| m.inspected).product()
 }
 
-fn parse_input(input: String) -> Vec<Monkey> {
+fn parse_input(input: &str) -> Result<Vec<Monkey>, ParseError> {
     let mut monkeys = Vec::new();
     let mut lines = input.trim_end().lines();
     loop {
         if lines.next().is_none() {
             break;
         }
-        let items = lines.next().map(parse_items).unwrap();
-        let operation = lines.next().map(parse_operation).unwrap();
-        let divisible_by = parse_end(lines.next(), "  Test: divisible by ");
-        let on_true = parse_end(lines.next(), "    If true: throw to monkey ");
-        let on_false = parse_end(lines.next(), "    If false: throw to monkey ");
+        let items = parse_items(input, next_line(input, &mut lines)?)?;
+        let operation = parse_operation(input, next_line(input, &mut lines)?)?;
+        let divisible_by = parse_end(input, lines.next(), "  Test: divisible by ")?;
+        let on_true = parse_end(input, lines.next(), "    If true: throw to monkey ")?;
+        let on_false = parse_end(input, lines.next(), "    If false: throw to monkey ")?;
         monkeys.push(Monkey {
             items,
             operation,
@@ -99,48 +108,40 @@ fn parse_input(input: String) -> Vec<Monkey> {
         });
         lines.next();
     }
-    monkeys
+    Ok(monkeys)
+}
+
+fn next_line<'a>(input: &str, lines: &mut std::str::Lines<'a>) -> Result<&'a str, ParseError> {
+    lines
+        .next()
+        .ok_or_else(|| ParseError::at_end(input, "Monkey description ends early"))
 }
 
-fn parse_items(line: &str) -> Vec<u64> {
+fn parse_items(input: &str, line: &str) -> Result<Vec<u64>, ParseError> {
     line.trim_start_matches("  Starting items: ")
         .split(", ")
-        .map(|i| i.parse().unwrap())
+        .map(|i| parse::number(input, i))
         .collect()
 }
 
-fn parse_operation(line: &str) -> Operation {
+fn parse_operation(input: &str, line: &str) -> Result<Operation, ParseError> {
     let op = line.trim_start_matches("  Operation: new = old ");
     if op == "* old" {
-        Operation::Sqr()
+        Ok(Operation::Sqr())
     } else if op.starts_with('*') {
-        Operation::Multiply(op.trim_start_matches("* ").parse().unwrap())
+        Ok(Operation::Multiply(parse::number(
+            input,
+            op.trim_start_matches("* "),
+        )?))
     } else {
-        Operation::Add(op.trim_start_matches("+ ").parse().unwrap())
+        Ok(Operation::Add(parse::number(
+            input,
+            op.trim_start_matches("+ "),
+        )?))
     }
 }
 
-fn parse_end<T: FromStr>(line: Option<&str>, prefix: &str) -> T
-where
-    <T as FromStr>::Err: std::fmt::Debug,
-{
-    line.unwrap().trim_start_matches(prefix).parse().unwrap()
-}
-
-#[cfg(test)]
-mod tests {
-    use crate::input_utils::get_test_input;
-    use crate::puzzles::assert_eq_string;
-
-    use super::*;
-
-    #[test]
-    fn solves_first() {
-        assert_eq_string(String::from("10605"), solve_first(get_test_input(11)));
-    }
-
-    #[test]
-    fn solves_second() {
-        assert_eq_string(String::from("2713310158"), solve_second(get_test_input(11)));
-    }
+fn parse_end<T: FromStr>(input: &str, line: Option<&str>, prefix: &str) -> Result<T, ParseError> {
+    let line = line.ok_or_else(|| ParseError::at_end(input, "Monkey description ends early"))?;
+    parse::number(input, line.trim_start_matches(prefix))
 }
@@ -1,147 +1,103 @@
-use super::Result;
+use aoc_core::answer::Answer;
+use aoc_core::grid::Grid;
+use aoc_core::parse::ParseError;
+use aoc_core::point::Point2;
+use aoc_core::search;
+use aoc_core::solution::Solution;
 
-type MapWithStartAndEnd = (Vec<Vec<u8>>, (usize, usize), (usize, usize));
-type CheckAndGo =
-    fn(&mut Vec<(usize, usize)>, &mut [Vec<i32>], &[Vec<u8>], (usize, usize), (usize, usize)) -> ();
+type Position = Point2<usize>;
+type MapWithStartAndEnd = (Grid<u8>, Position, Position);
 
-pub fn solve_first(input: String) -> Result {
-    Result::Number(find_path(input, check_and_go))
-}
+pub struct Day12;
 
-pub fn solve_second(input: String) -> Result {
-    Result::Number(find_path(input, check_and_go_make_a_zero))
-}
+impl Solution for Day12 {
+    const DAY: u8 = 12;
+    const TITLE: &'static str = "Hill Climbing Algorithm";
+    type Input = MapWithStartAndEnd;
 
-fn find_path(input: String, check_and_go: CheckAndGo) -> u32 {
-    let (height_map, (start_x, start_y), (end_x, end_y)) = parse_input(input);
-    let h = height_map.len();
-    let w = height_map[0].len();
-    let mut shortest_path: Vec<Vec<i32>> = vec![vec![-1; w]; h];
-    shortest_path[start_x][start_y] = 0;
-    let mut to_check = vec![(start_x, start_y)];
-    while let Some((x, y)) = to_check.pop() {
-        if x + 1 < h {
-            check_and_go(
-                &mut to_check,
-                &mut shortest_path,
-                &height_map,
-                (x, y),
-                (x + 1, y),
-            );
-        }
-        if x > 0 {
-            check_and_go(
-                &mut to_check,
-                &mut shortest_path,
-                &height_map,
-                (x, y),
-                (x - 1, y),
-            );
-        }
-        if y + 1 < w {
-            check_and_go(
-                &mut to_check,
-                &mut shortest_path,
-                &height_map,
-                (x, y),
-                (x, y + 1),
-            );
-        }
-        if y > 0 {
-            check_and_go(
-                &mut to_check,
-                &mut shortest_path,
-                &height_map,
-                (x, y),
-                (x, y - 1),
-            );
-        }
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
     }
-    shortest_path[end_x][end_y] as u32
-}
 
-fn check_and_go(
-    to_check: &mut Vec<(usize, usize)>,
-    shortest_path: &mut [Vec<i32>],
-    height_map: &[Vec<u8>],
-    (from_x, from_y): (usize, usize),
-    (to_x, to_y): (usize, usize),
-) {
-    if (shortest_path[to_x][to_y] == -1
-        || shortest_path[from_x][from_y] + 1 < shortest_path[to_x][to_y])
-        && height_map[from_x][from_y] + 1 >= height_map[to_x][to_y]
-    {
-        shortest_path[to_x][to_y] = shortest_path[from_x][from_y] + 1;
-        to_check.push((to_x, to_y));
+    fn part1(input: &Self::Input) -> Answer {
+        let (height_map, start, end) = input;
+        Answer::from(
+            steps_to_end(height_map, *start, *end)
+                .expect("parse_input checked that the end is reachable"),
+        )
     }
-}
 
-fn check_and_go_make_a_zero(
-    to_check: &mut Vec<(usize, usize)>,
-    shortest_path: &mut [Vec<i32>],
-    height_map: &[Vec<u8>],
-    (from_x, from_y): (usize, usize),
-    (to_x, to_y): (usize, usize),
-) {
-    if shortest_path[to_x][to_y] == -1 && height_map[to_x][to_y] == b'a' {
-        shortest_path[to_x][to_y] = 0;
-        to_check.push((to_x, to_y));
-    } else if (shortest_path[to_x][to_y] == -1
-        || shortest_path[from_x][from_y] + 1 < shortest_path[to_x][to_y])
-        && height_map[from_x][from_y] + 1 >= height_map[to_x][to_y]
-    {
-        shortest_path[to_x][to_y] = shortest_path[from_x][from_y] + 1;
-        to_check.push((to_x, to_y));
+    /// The start is one of the lowest squares, so there is always one that leads to the end
+    fn part2(input: &Self::Input) -> Answer {
+        let (height_map, _, end) = input;
+        let search = search::bfs(
+            [*end],
+            |&to| climbable_to(height_map, to),
+            |&position| height_map[position] == b'a',
+        );
+        Answer::from(
+            search
+                .goal_cost()
+                .expect("parse_input checked that the start leads to the end"),
+        )
     }
 }
 
-fn parse_input(input: String) -> MapWithStartAndEnd {
-    let mut start: Option<(usize, usize)> = None;
-    let mut end: Option<(usize, usize)> = None;
-    let height_map = input
-        .trim_end()
-        .split('\n')
-        .enumerate()
-        .map(|(i, line)| {
-            line.as_bytes()
-                .iter()
-                .enumerate()
-                .map(|(j, &e)| match e {
-                    b'S' => {
-                        start = Some((i, j));
-                        b'a'
-                    }
-                    b'E' => {
-                        end = Some((i, j));
-                        b'z'
-                    }
-                    e => e,
-                })
-                .collect()
-        })
-        .collect();
-    (height_map, start.unwrap(), end.unwrap())
+/// Fewest steps from the start to the end, `None` when it can't be reached
+fn steps_to_end(height_map: &Grid<u8>, start: Position, end: Position) -> Option<usize> {
+    search::bfs(
+        [start],
+        |&from| climbable(height_map, from),
+        |&position| position == end,
+    )
+    .goal_cost()
 }
 
-#[cfg(test)]
-mod tests {
-    use crate::puzzles::assert_eq_number;
-
-    use super::*;
+/// Squares one step away that are at most one higher
+fn climbable(height_map: &Grid<u8>, from: Position) -> impl Iterator<Item = Position> + '_ {
+    height_map
+        .neighbors4(from)
+        .filter(move |&to| height_map[to] <= height_map[from] + 1)
+}
 
-    static RAW_INPUT: &str = "Sabqponm\n\
-    abcryxxl\n\
-    accszExk\n\
-    acctuvwj\n\
-    abdefghi\n";
+/// Squares one step away from which the square can be climbed, for searching downhill from the end
+fn climbable_to(height_map: &Grid<u8>, to: Position) -> impl Iterator<Item = Position> + '_ {
+    height_map
+        .neighbors4(to)
+        .filter(move |&from| height_map[to] <= height_map[from] + 1)
+}
 
-    #[test]
-    fn solves_first() {
-        assert_eq_number(31, solve_first(String::from(RAW_INPUT)));
+fn parse_input(input: &str) -> Result<MapWithStartAndEnd, ParseError> {
+    let mut height_map = Grid::parse(input, |c| c.is_ascii_alphabetic().then_some(c as u8))?;
+    match (height_map.find(&b'S'), height_map.find(&b'E')) {
+        (Some(start), Some(end)) => {
+            height_map[start] = b'a';
+            height_map[end] = b'z';
+            if steps_to_end(&height_map, start, end).is_none() {
+                let end_mark = input.find('E').map_or(input, |i| &input[i..=i]);
+                return Err(ParseError::at(
+                    input,
+                    end_mark,
+                    "The end can't be reached from the start",
+                ));
+            }
+            Ok((height_map, start, end))
+        }
+        _ => Err(ParseError::at_end(
+            input,
+            "Expected both start S and end E on the map",
+        )),
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
     #[test]
-    fn solves_second() {
-        assert_eq_number(29, solve_second(String::from(RAW_INPUT)));
+    fn rejects_unreachable_ends() {
+        let error = Day12::parse("Sbcd\nxxxE\n").err().unwrap();
+        assert_eq!((2, 4), (error.line, error.column));
+        assert_eq!("The end can't be reached from the start", error.message);
     }
 }
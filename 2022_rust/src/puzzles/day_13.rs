@@ -1,54 +1,65 @@
 use std::cmp::Ordering;
 
-use super::Result;
+use itertools::Itertools;
+
+use aoc_core::answer::Answer;
+use aoc_core::parse::{self, ParseError};
+use aoc_core::solution::Solution;
 
 #[derive(Debug)]
-enum Packet {
+pub enum Packet {
     Array(Vec<Packet>),
     Number(u32),
 }
 
-pub fn solve_first(input: String) -> Result {
-    let packet_pairs = parse_input(input);
-    let in_right_order = packet_pairs
-        .iter()
-        .enumerate()
-        .map(|(i, (p1, p2))| {
-            if check_order(p1, p2) != Ordering::Greater {
-                (i + 1) as u32
-            } else {
-                0
-            }
-        })
-        .sum();
-    Result::Number(in_right_order)
-}
+pub struct Day13;
 
-pub fn solve_second(input: String) -> Result {
-    let parsed_pairs = parse_input(input);
-    let mut packets: Vec<&Packet> = parsed_pairs
-        .iter()
-        .flat_map(|(p1, p2)| vec![p1, p2])
-        .collect();
-    let divider_2 = Packet::Array(vec![Packet::Array(vec![Packet::Number(2)])]);
-    let divider_6 = Packet::Array(vec![Packet::Array(vec![Packet::Number(6)])]);
-    packets.push(&divider_2);
-    packets.push(&divider_6);
-    packets.sort_by(|p1, p2| check_order(p1, p2));
-    let decoder = packets
-        .iter()
-        .enumerate()
-        .map(|(i, p)| {
-            if check_order(p, &divider_2) == Ordering::Equal
-                || check_order(p, &divider_6) == Ordering::Equal
-            {
-                (i + 1) as u32
-            } else {
-                1
-            }
-        })
-        .product();
-    Result::Number(decoder)
+impl Solution for Day13 {
+    const DAY: u8 = 13;
+    const TITLE: &'static str = "Distress Signal";
+    type Input = Vec<(Packet, Packet)>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        let in_right_order: u32 = input
+            .iter()
+            .enumerate()
+            .map(|(i, (p1, p2))| {
+                if check_order(p1, p2) != Ordering::Greater {
+                    (i + 1) as u32
+                } else {
+                    0
+                }
+            })
+            .sum();
+        Answer::from(in_right_order)
+    }
+
+    fn part2(input: &Self::Input) -> Answer {
+        let mut packets: Vec<&Packet> = input.iter().flat_map(|(p1, p2)| vec![p1, p2]).collect();
+        let divider_2 = Packet::Array(vec![Packet::Array(vec![Packet::Number(2)])]);
+        let divider_6 = Packet::Array(vec![Packet::Array(vec![Packet::Number(6)])]);
+        packets.push(&divider_2);
+        packets.push(&divider_6);
+        packets.sort_by(|p1, p2| check_order(p1, p2));
+        let decoder: u32 = packets
+            .iter()
+            .enumerate()
+            .map(|(i, p)| {
+                if check_order(p, &divider_2) == Ordering::Equal
+                    || check_order(p, &divider_6) == Ordering::Equal
+                {
+                    (i + 1) as u32
+                } else {
+                    1
+                }
+            })
+            .product();
+        Answer::from(decoder)
+    }
 }
 
 fn check_order(p1: &Packet, p2: &Packet) -> Ordering {
@@ -60,79 +71,53 @@ fn check_order(p1: &Packet, p2: &Packet) -> Ordering {
         (arr_packet, Packet::Number(n)) => {
             check_order(arr_packet, &Packet::Array(vec![Packet::Number(*n)]))
         }
-        (Packet::Array(a1), Packet::Array(a2)) => a1.iter().cmp_by(a2, check_order),
+        (Packet::Array(a1), Packet::Array(a2)) => a1
+            .iter()
+            .zip(a2)
+            .map(|(p1, p2)| check_order(p1, p2))
+            .find(|order| *order != Ordering::Equal)
+            .unwrap_or_else(|| a1.len().cmp(&a2.len())),
     }
 }
 
-fn parse_input(input: String) -> Vec<(Packet, Packet)> {
+fn parse_input(input: &str) -> Result<Vec<(Packet, Packet)>, ParseError> {
     input
         .split('\n')
-        .array_chunks::<3>()
-        .map(|[pair_left, pair_right, _empty]| (parse_line(pair_left).0, parse_line(pair_right).0))
+        .tuples()
+        .map(|(pair_left, pair_right, _empty)| {
+            Ok((
+                parse_line(input, pair_left)?.0,
+                parse_line(input, pair_right)?.0,
+            ))
+        })
         .collect()
 }
 
-fn parse_line(line: &str) -> (Packet, usize) {
+fn parse_line(input: &str, line: &str) -> Result<(Packet, usize), ParseError> {
     if line.starts_with('[') {
         let mut arr = Vec::new();
         let mut cur_pos = 1;
-        while line.chars().nth(cur_pos).unwrap() != ']' {
-            let (packet, next_pos) = parse_line(&line[cur_pos..]);
+        while next_byte(input, line, cur_pos)? != b']' {
+            let (packet, next_pos) = parse_line(input, &line[cur_pos..])?;
             cur_pos += next_pos;
-            if (line.chars().nth(cur_pos)).unwrap() == ',' {
+            if next_byte(input, line, cur_pos)? == b',' {
                 cur_pos += 1;
             }
             arr.push(packet);
         }
-        (Packet::Array(arr), cur_pos + 1)
+        Ok((Packet::Array(arr), cur_pos + 1))
     } else {
-        let rest_start = line
-            .find(|c: char| !c.is_numeric())
-            .unwrap_or(line.len() - 1);
-        (
-            Packet::Number(line[0..rest_start].parse().unwrap()),
+        let rest_start = line.find(|c: char| !c.is_numeric()).unwrap_or(line.len());
+        Ok((
+            Packet::Number(parse::number(input, &line[0..rest_start])?),
             rest_start,
-        )
+        ))
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use crate::puzzles::assert_eq_number;
-
-    use super::*;
-
-    static RAW_INPUT: &str = "[1,1,3,1,1]\n\
-    [1,1,5,1,1]\n\
-    \n\
-    [[1],[2,3,4]]\n\
-    [[1],4]\n\
-    \n\
-    [9]\n\
-    [[8,7,6]]\n\
-    \n\
-    [[4,4],4,4]\n\
-    [[4,4],4,4,4]\n\
-    \n\
-    [7,7,7,7]\n\
-    [7,7,7]\n\
-    \n\
-    []\n\
-    [3]\n\
-    \n\
-    [[[]]]\n\
-    [[]]\n\
-    \n\
-    [1,[2,[3,[4,[5,6,7]]]],8,9]\n\
-    [1,[2,[3,[4,[5,6,0]]]],8,9]\n";
-
-    #[test]
-    fn solves_first() {
-        assert_eq_number(13, solve_first(String::from(RAW_INPUT)));
-    }
-
-    #[test]
-    fn solves_second() {
-        assert_eq_number(140, solve_second(String::from(RAW_INPUT)));
-    }
+fn next_byte(input: &str, line: &str, pos: usize) -> Result<u8, ParseError> {
+    line.as_bytes()
+        .get(pos)
+        .copied()
+        .ok_or_else(|| ParseError::at(input, &line[line.len()..], "List is not closed"))
 }
@@ -1,117 +1,113 @@
-use std::cmp::max;
 use std::collections::HashSet;
 
 use regex::Regex;
 
-use super::Result;
-use crate::utils::unwrap_match_to_i64;
+use crate::utils::{capture_to_number, captures};
+use aoc_core::answer::Answer;
+use aoc_core::interval::IntervalSet;
+use aoc_core::params::{Param, Params};
+use aoc_core::parse::ParseError;
+use aoc_core::point::Point2;
+use aoc_core::solution::Solution;
+
+type SensorAndBeacon = (Point2<i64>, Point2<i64>);
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    const DAY: u8 = 15;
+    const TITLE: &'static str = "Beacon Exclusion Zone";
+    const PARAMS: &'static [Param] = &[
+        Param::new::<i64>(
+            "row",
+            2_000_000,
+            "Row to count the positions without a beacon in",
+        ),
+        Param::new::<i64>(
+            "bound",
+            4_000_000,
+            "Largest coordinate the distress beacon can have",
+        ),
+    ];
+    type Input = Vec<SensorAndBeacon>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
 
-pub fn solve_first(input: String) -> Result {
-    _solve_first(input, 2_000_000)
-}
+    fn part1(input: &Self::Input) -> Answer {
+        Self::part1_with(input, &Params::defaults(Self::PARAMS))
+    }
+
+    fn part2(input: &Self::Input) -> Answer {
+        Self::part2_with(input, &Params::defaults(Self::PARAMS))
+    }
 
-fn _solve_first(input: String, row: i64) -> Result {
-    let sensors_and_beacons = parse_input(input);
+    fn part1_with(input: &Self::Input, params: &Params) -> Answer {
+        solve_first_with_row(input, params.get("row"))
+    }
+
+    fn part2_with(input: &Self::Input, params: &Params) -> Answer {
+        solve_second_with_bound(input, params.get("bound"))
+    }
+}
 
-    let mut sensors_with_reach: Vec<((i64, i64), i64)> = Vec::new();
+fn solve_first_with_row(sensors_and_beacons: &[SensorAndBeacon], row: i64) -> Answer {
+    let mut sensors_with_reach: Vec<(Point2<i64>, i64)> = Vec::new();
     let mut beacon_or_sensor_in_row: HashSet<i64> = HashSet::new();
 
-    for (s, b) in sensors_and_beacons {
-        let bs_distance = get_manhattan_distance(s, b);
-        sensors_with_reach.push((s, bs_distance));
-        if s.1 == row {
-            beacon_or_sensor_in_row.insert(s.0);
+    for &(s, b) in sensors_and_beacons {
+        sensors_with_reach.push((s, s.manhattan(b)));
+        if s.y == row {
+            beacon_or_sensor_in_row.insert(s.x);
         }
-        if b.1 == row {
-            beacon_or_sensor_in_row.insert(b.0);
+        if b.y == row {
+            beacon_or_sensor_in_row.insert(b.x);
         }
     }
 
-    let no_beacon_intervals = get_no_beacon_intervals(&sensors_with_reach, row);
-    let no_beacon_in_row = no_beacon_intervals
-        .iter()
-        .map(|i| i.1 - i.0 + 1)
-        .sum::<i64>() as u32;
-
-    Result::Number(no_beacon_in_row - beacon_or_sensor_in_row.len() as u32)
-}
+    let no_beacon_in_row = get_no_beacon_intervals(&sensors_with_reach, row).total_len();
 
-pub fn solve_second(input: String) -> Result {
-    _solve_second(input, 0, 4_000_000)
+    Answer::from(no_beacon_in_row - beacon_or_sensor_in_row.len() as u128)
 }
 
-fn _solve_second(input: String, min: i64, max: i64) -> Result {
-    let sensors_and_beacons = parse_input(input);
-
-    let mut sensors_with_reach: Vec<((i64, i64), i64)> = Vec::new();
-
-    for (s, b) in sensors_and_beacons {
-        let bs_distance = get_manhattan_distance(s, b);
-        sensors_with_reach.push((s, bs_distance));
-    }
+/// Tuning frequency of the only position from 0 to `bound` in both coordinates
+/// that no sensor rules out, panics when every position is ruled out
+fn solve_second_with_bound(sensors_and_beacons: &[SensorAndBeacon], bound: i64) -> Answer {
+    let sensors_with_reach: Vec<(Point2<i64>, i64)> = sensors_and_beacons
+        .iter()
+        .map(|&(s, b)| (s, s.manhattan(b)))
+        .collect();
 
     let mut possible_beacon: Option<(i128, i128)> = None;
 
-    for i in min..=max {
+    for i in 0..=bound {
         let no_beacon_intervals = get_no_beacon_intervals(&sensors_with_reach, i);
-        match no_beacon_intervals.last() {
-            Some((_, e)) if *e < max => {
-                possible_beacon = Some(((*e + 1) as i128, i as i128));
-                break;
-            }
-            Some((s, _)) if *s > min => {
-                possible_beacon = Some(((*s - 1) as i128, i as i128));
-                break;
-            }
-            None => panic!("Panic!!!"),
-            _ => (),
+        if let Some(gap) = no_beacon_intervals.gaps(0..=bound).first() {
+            possible_beacon = Some((*gap.start() as i128, i as i128));
+            break;
         }
     }
 
-    let tunning_freq = possible_beacon.map(|(x, y)| x * 4_000_000 + y).unwrap();
-
-    Result::String(tunning_freq.to_string())
-}
-
-fn get_manhattan_distance((x1, y1): (i64, i64), (x2, y2): (i64, i64)) -> i64 {
-    (x1 - x2).abs() + (y1 - y2).abs()
+    let (x, y) = possible_beacon.unwrap_or_else(|| panic!("No possible beacon up to {bound}"));
+    Answer::from(x * 4_000_000 + y)
 }
 
 fn get_no_beacon_intervals(
-    sensors_with_reach: &Vec<((i64, i64), i64)>,
+    sensors_with_reach: &[(Point2<i64>, i64)],
     row: i64,
-) -> Vec<(i64, i64)> {
-    let mut no_beacon_intervals: Vec<(i64, i64)> = Vec::new();
-
-    for (s, reach) in sensors_with_reach {
-        let row_distance = (s.1 - row).abs();
-        let row_border_distance = reach - row_distance;
-        if row_border_distance < 0 {
-            continue;
-        }
-        no_beacon_intervals.push(((s.0 - row_border_distance), (s.0 + row_border_distance)));
-    }
-
-    optimize_intervals(no_beacon_intervals)
-}
-
-fn optimize_intervals(mut intervals: Vec<(i64, i64)>) -> Vec<(i64, i64)> {
-    intervals.sort_by(|(a, _), (b, _)| a.cmp(b));
-    let mut optimized_intervals: Vec<(i64, i64)> = Vec::new();
-    for i2 in intervals {
-        match optimized_intervals.pop() {
-            None => optimized_intervals.push(i2),
-            Some((s1, e1)) if i2.0 - 1 <= e1 => optimized_intervals.push((s1, max(e1, i2.1))),
-            Some(i1) => {
-                optimized_intervals.push(i1);
-                optimized_intervals.push(i2);
-            }
-        };
-    }
-    optimized_intervals
+) -> IntervalSet<i64> {
+    sensors_with_reach
+        .iter()
+        .filter_map(|(s, reach)| {
+            let row_border_distance = reach - (s.y - row).abs();
+            (row_border_distance >= 0)
+                .then(|| (s.x - row_border_distance)..=(s.x + row_border_distance))
+        })
+        .collect()
 }
 
-fn parse_input(input: String) -> Vec<((i64, i64), (i64, i64))> {
+fn parse_input(input: &str) -> Result<Vec<SensorAndBeacon>, ParseError> {
     let line_re: Regex =
         Regex::new(r"^Sensor at x=(-?\d+), y=(-?\d+): closest beacon is at x=(-?\d+), y=(-?\d+)$")
             .unwrap();
@@ -119,54 +115,33 @@ fn parse_input(input: String) -> Vec<((i64, i64), (i64, i64))> {
         .trim_end()
         .split('\n')
         .map(|l| {
-            let captures = line_re
-                .captures(l)
-                .expect("Input line is not of expected format");
-            (
-                (
-                    unwrap_match_to_i64(captures.get(1)),
-                    unwrap_match_to_i64(captures.get(2)),
+            let captures = captures(input, &line_re, l)?;
+            Ok((
+                Point2::new(
+                    capture_to_number(input, &captures, 1)?,
+                    capture_to_number(input, &captures, 2)?,
                 ),
-                (
-                    unwrap_match_to_i64(captures.get(3)),
-                    unwrap_match_to_i64(captures.get(4)),
+                Point2::new(
+                    capture_to_number(input, &captures, 3)?,
+                    capture_to_number(input, &captures, 4)?,
                 ),
-            )
+            ))
         })
         .collect()
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::puzzles::{assert_eq_number, assert_eq_string};
-
     use super::*;
 
-    static RAW_INPUT: &str = "Sensor at x=2, y=18: closest beacon is at x=-2, y=15\n\
-    Sensor at x=9, y=16: closest beacon is at x=10, y=16\n\
-    Sensor at x=13, y=2: closest beacon is at x=15, y=3\n\
-    Sensor at x=12, y=14: closest beacon is at x=10, y=16\n\
-    Sensor at x=10, y=20: closest beacon is at x=10, y=16\n\
-    Sensor at x=14, y=17: closest beacon is at x=10, y=16\n\
-    Sensor at x=8, y=7: closest beacon is at x=2, y=10\n\
-    Sensor at x=2, y=0: closest beacon is at x=2, y=10\n\
-    Sensor at x=0, y=11: closest beacon is at x=2, y=10\n\
-    Sensor at x=20, y=14: closest beacon is at x=25, y=17\n\
-    Sensor at x=17, y=20: closest beacon is at x=21, y=22\n\
-    Sensor at x=16, y=7: closest beacon is at x=15, y=3\n\
-    Sensor at x=14, y=3: closest beacon is at x=15, y=3\n\
-    Sensor at x=20, y=1: closest beacon is at x=15, y=3\n";
-
-    #[test]
-    fn solves_first() {
-        assert_eq_number(26, _solve_first(String::from(RAW_INPUT), 10));
-    }
-
     #[test]
-    fn solves_second() {
-        assert_eq_string(
-            String::from("56000011"),
-            _solve_second(String::from(RAW_INPUT), 0, 20),
+    #[should_panic(expected = "No possible beacon up to 4")]
+    fn fails_when_there_is_no_possible_beacon() {
+        let sensors_and_beacons = [(Point2::new(2, 2), Point2::new(0, 0))];
+        assert_eq!(
+            Answer::from(8),
+            solve_first_with_row(&sensors_and_beacons, 2)
         );
+        solve_second_with_bound(&sensors_and_beacons, 4);
     }
 }
@@ -1,252 +1,308 @@
-use itertools::Itertools;
-use std::cmp::{max, min};
+use std::cmp::{max, Reverse};
 use std::collections::{HashMap, HashSet};
 
 use regex::Regex;
 
-use super::Result;
-use crate::utils::unwrap_match_to_i64;
+use crate::utils::{capture_to_number, captures};
+use aoc_core::answer::Answer;
+use aoc_core::params::{Param, Params};
+use aoc_core::parse::ParseError;
+use aoc_core::search;
+use aoc_core::solution::{Metadata, Solution};
 
 #[derive(Debug, Clone)]
-struct Valve {
+pub struct Valve {
     flow_rate: u32,
     tunnel_to: HashMap<String, i32>,
 }
 
-pub fn solve_first(input: String) -> Result {
-    let mut valve_system = parse_input(input);
-    optimize_valve_system(&mut valve_system);
-    floyd_warshall_the_system(&mut valve_system);
+pub struct Day16;
 
-    let mut not_visited: HashSet<String> = valve_system
-        .keys()
-        .into_iter()
-        .map(|k| k.to_string())
-        .collect();
-    not_visited.remove("AA");
-    let pressure = find_most_pressure(
-        &valve_system,
-        &vec!["AA".to_string()],
-        &vec![],
-        30,
-        &not_visited,
-        0,
-    );
+impl Solution for Day16 {
+    const DAY: u8 = 16;
+    const TITLE: &'static str = "Proboscidea Volcanium";
+    const METADATA: Metadata = Metadata {
+        notes: Some(
+            "Finds the most pressure for every set of valves that can be opened, part 2 combines the best two disjoint sets",
+        ),
+        tags: &[],
+    };
+    const PARAMS: &'static [Param] = &[
+        Param::new::<u32>("minutes", 30, "Minutes until the volcano erupts"),
+        Param::new::<u32>(
+            "elephant_minutes",
+            26,
+            "Minutes left after teaching the elephant to open valves",
+        ),
+    ];
+    type Input = HashMap<String, Valve>;
 
-    Result::Number(pressure)
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let mut valve_system = parse_input(input)?;
+        optimize_valve_system(&mut valve_system);
+        if valve_system.keys().filter(|name| *name != "AA").count() > 64 {
+            return Err(ParseError::at_end(
+                input,
+                "Expected at most 64 valves with a flow rate",
+            ));
+        }
+        floyd_warshall_the_system(&mut valve_system);
+        Ok(valve_system)
+    }
+
+    fn part1(valve_system: &Self::Input) -> Answer {
+        Self::part1_with(valve_system, &Params::defaults(Self::PARAMS))
+    }
+
+    fn part2(valve_system: &Self::Input) -> Answer {
+        Self::part2_with(valve_system, &Params::defaults(Self::PARAMS))
+    }
+
+    fn part1_with(valve_system: &Self::Input, params: &Params) -> Answer {
+        let most_pressure = most_pressure_by_opened(valve_system, params.get("minutes"));
+        Answer::from(most_pressure.into_values().max().unwrap_or(0))
+    }
+
+    /// We and the elephant open disjoint sets of valves, so the best pair of
+    /// disjoint sets is the most pressure released together
+    fn part2_with(valve_system: &Self::Input, params: &Params) -> Answer {
+        let most_pressure = most_pressure_by_opened(valve_system, params.get("elephant_minutes"));
+        let mut by_pressure: Vec<(u64, u32)> = most_pressure.into_iter().collect();
+        by_pressure.sort_unstable_by_key(|&(_, pressure)| Reverse(pressure));
+
+        let mut together = 0;
+        for (i, &(ours, our_pressure)) in by_pressure.iter().enumerate() {
+            // The elephant's set releases at most as much as ours from here on
+            if our_pressure * 2 <= together {
+                break;
+            }
+            for &(elephants, elephant_pressure) in &by_pressure[i..] {
+                if our_pressure + elephant_pressure <= together {
+                    break;
+                }
+                if ours & elephants == 0 {
+                    together = our_pressure + elephant_pressure;
+                }
+            }
+        }
+        Answer::from(together)
+    }
 }
 
-pub fn solve_second(input: String) -> Result {
-    let mut valve_system = parse_input(input);
-    optimize_valve_system(&mut valve_system);
-    floyd_warshall_the_system(&mut valve_system);
+/// The valves there is a tunnel to from AA, the others can never be opened
+fn reachable_valves(valve_system: &HashMap<String, Valve>) -> HashSet<String> {
+    valve_system
+        .get("AA")
+        .map(|start| start.tunnel_to.keys().cloned().collect())
+        .unwrap_or_default()
+}
 
-    let mut not_visited: HashSet<String> = valve_system
-        .keys()
+/// The most pressure released by opening each set of valves within the minutes,
+/// for every set that can be opened in time, the sets as bits of `valves`
+fn most_pressure_by_opened(
+    valve_system: &HashMap<String, Valve>,
+    minutes: u32,
+) -> HashMap<u64, u32> {
+    let mut valves: Vec<(&str, u32)> = reachable_valves(valve_system)
         .into_iter()
-        .map(|k| k.to_string())
+        .filter(|name| name != "AA")
+        .map(|name| {
+            let (name, valve) = valve_system.get_key_value(&name).unwrap();
+            (name.as_str(), valve.flow_rate)
+        })
         .collect();
-    not_visited.remove("AA");
-
-    let pressure = find_most_pressure(
-        &valve_system,
-        &vec!["AA".to_string(), "AA".to_string()],
-        &vec![],
-        26,
-        &not_visited,
+    valves.sort_unstable();
+
+    let mut most_pressure = HashMap::new();
+    open_valves(
+        valve_system,
+        &valves,
+        "AA",
+        minutes,
         0,
+        0,
+        &mut most_pressure,
     );
-
-    Result::Number(pressure)
+    most_pressure
 }
 
-fn find_most_pressure(
+fn open_valves(
     valve_system: &HashMap<String, Valve>,
-    current_free: &Vec<String>,
-    current_moving: &Vec<(String, u32)>,
+    valves: &[(&str, u32)],
+    from: &str,
     minutes_left: u32,
-    not_visited: &HashSet<String>,
+    opened: u64,
     released_pressure: u32,
-) -> u32 {
-    if minutes_left == 0 {
-        return 0;
-    }
-    if not_visited.is_empty() && current_moving.is_empty() {
-        return released_pressure * minutes_left;
-    }
-
-    let mut next_free: Vec<String> = Vec::new();
-    let mut next_moving_tmp: Vec<(String, u32)> = Vec::new();
-    let mut next_released_pressure = released_pressure;
+    most_pressure: &mut HashMap<u64, u32>,
+) {
+    let most = most_pressure.entry(opened).or_insert(0);
+    *most = max(*most, released_pressure);
 
-    for (to_valve, distance) in current_moving {
-        if *distance == 1 {
-            next_released_pressure += valve_system.get(to_valve).unwrap().flow_rate;
-            next_free.push(to_valve.clone());
-        } else {
-            next_moving_tmp.push((to_valve.clone(), distance - 1))
+    let tunnels = &valve_system[from].tunnel_to;
+    for (i, &(valve, flow_rate)) in valves.iter().enumerate() {
+        let bit = 1 << i;
+        if opened & bit != 0 {
+            continue;
         }
-    }
-
-    let mut most_pressure = next_released_pressure * (minutes_left - 1);
-    if not_visited.is_empty() || current_free.is_empty() {
-        let next_pressure = find_most_pressure(
+        // Walking there and opening the valve has to leave time for it to release pressure
+        let Some(minutes_left) = tunnels
+            .get(valve)
+            .and_then(|&distance| minutes_left.checked_sub(distance as u32 + 1))
+            .filter(|&minutes_left| minutes_left > 0)
+        else {
+            continue;
+        };
+        open_valves(
             valve_system,
-            &next_free,
-            &next_moving_tmp,
-            minutes_left - 1,
-            not_visited,
-            next_released_pressure,
+            valves,
+            valve,
+            minutes_left,
+            opened | bit,
+            released_pressure + flow_rate * minutes_left,
+            most_pressure,
         );
-        most_pressure = max(most_pressure, next_pressure);
-    } else {
-        let not_visited_vec: Vec<String> = not_visited.iter().map(|v| v.to_string()).collect();
-        let all_permutations = not_visited_vec.into_iter().permutations(current_free.len());
-
-        for perm in all_permutations {
-            let mut next_moving: Vec<(String, u32)> = next_moving_tmp.clone();
-            let mut next_not_visited = not_visited.clone();
-            for (from_valve, to_valve) in current_free.clone().into_iter().zip_eq(perm) {
-                next_not_visited.remove(&to_valve);
-                let distance = *valve_system
-                    .get(&from_valve)
-                    .unwrap()
-                    .tunnel_to
-                    .get(&to_valve)
-                    .unwrap() as u32;
-                next_moving.push((to_valve, distance));
-            }
-            let next_pressure = find_most_pressure(
-                valve_system,
-                &next_free,
-                &next_moving,
-                minutes_left - 1,
-                &next_not_visited,
-                next_released_pressure,
-            );
-            most_pressure = max(most_pressure, next_pressure);
-        }
     }
-
-    released_pressure + most_pressure
 }
 
 fn floyd_warshall_the_system(valve_system: &mut HashMap<String, Valve>) {
-    for (valve_name, valve) in valve_system.iter_mut() {
-        valve.tunnel_to.insert(valve_name.clone(), 0);
-    }
     let valve_names: Vec<String> = valve_system.keys().cloned().collect();
-    for k in &valve_names {
-        for i in &valve_names {
-            for j in &valve_names {
-                let k_to_j = valve_system
-                    .get(k)
-                    .and_then(|v| v.tunnel_to.get(j))
-                    .copied();
-                let valve = valve_system.get_mut(i).unwrap();
-                let i_to_j = valve.tunnel_to.get(j);
-                let i_to_k = valve.tunnel_to.get(k);
-                let new_i_to_j = i_to_k.and_then(|x| k_to_j.map(|y| x + y));
-                if let Some(n) =
-                    new_i_to_j.and_then(|n| i_to_j.map_or(Some(n), |o| Some(min(n, *o))))
-                {
-                    valve.tunnel_to.insert(j.to_string(), n);
-                }
-            }
+    let indices: HashMap<&str, usize> = valve_names
+        .iter()
+        .enumerate()
+        .map(|(i, name)| (name.as_str(), i))
+        .collect();
+    let indices = &indices;
+    let tunnels = valve_names.iter().enumerate().flat_map(|(from, name)| {
+        valve_system[name]
+            .tunnel_to
+            .iter()
+            .filter_map(move |(to, &distance)| Some((from, *indices.get(to.as_str())?, distance)))
+    });
+    let distances = search::floyd_warshall(valve_names.len(), tunnels);
+    for (valve_name, row) in valve_names.iter().zip(distances) {
+        if let Some(valve) = valve_system.get_mut(valve_name) {
+            valve.tunnel_to = valve_names
+                .iter()
+                .zip(row)
+                .filter_map(|(to, distance)| Some((to.clone(), distance?)))
+                .collect();
         }
     }
 }
 
+/// Replaces the valves without flow by tunnels between their neighbours, the
+/// tunnels lead both ways as checked by [`parse_input`]
 fn optimize_valve_system(valve_system: &mut HashMap<String, Valve>) {
-    'outer: loop {
-        let maybe_zero_valve = valve_system
-            .iter_mut()
-            .find(|(k, v)| *k != "AA" && v.flow_rate == 0)
-            .map(|(k, _)| k.clone());
-        match maybe_zero_valve {
-            Some(zero_valve_name) => {
-                let zero_valve = valve_system.remove(&zero_valve_name).unwrap();
-                for from_valve_name in zero_valve.tunnel_to.keys() {
-                    let from_valve = valve_system.get_mut(from_valve_name).unwrap();
-                    let from_zero_distance = from_valve.tunnel_to.remove(&zero_valve_name).unwrap();
-                    for to_valve_name in zero_valve.tunnel_to.keys() {
-                        if to_valve_name != from_valve_name {
-                            let &to_zero_distance =
-                                zero_valve.tunnel_to.get(to_valve_name).unwrap();
-                            let distance = from_zero_distance + to_zero_distance;
-                            let old_distance = from_valve.tunnel_to.get(to_valve_name);
-                            if old_distance.is_none() || *old_distance.unwrap() > distance {
-                                from_valve.tunnel_to.insert(
-                                    to_valve_name.clone(),
-                                    from_zero_distance + to_zero_distance,
-                                );
-                            }
-                        }
+    while let Some((zero_valve_name, zero_valve)) = valve_system
+        .iter()
+        .find(|(k, v)| *k != "AA" && v.flow_rate == 0)
+        .map(|(k, _)| k.clone())
+        .and_then(|name| valve_system.remove_entry(&name))
+    {
+        for from_valve_name in zero_valve.tunnel_to.keys() {
+            let Some(from_valve) = valve_system.get_mut(from_valve_name) else {
+                continue;
+            };
+            let Some(from_zero_distance) = from_valve.tunnel_to.remove(&zero_valve_name) else {
+                continue;
+            };
+            for (to_valve_name, &to_zero_distance) in &zero_valve.tunnel_to {
+                if to_valve_name != from_valve_name {
+                    let distance = from_zero_distance + to_zero_distance;
+                    let old_distance = from_valve.tunnel_to.get(to_valve_name);
+                    if old_distance.is_none_or(|&old_distance| old_distance > distance) {
+                        from_valve.tunnel_to.insert(to_valve_name.clone(), distance);
                     }
                 }
             }
-            None => {
-                break 'outer;
-            }
-        };
+        }
     }
 }
 
-fn parse_input(input: String) -> HashMap<String, Valve> {
+fn parse_input(input: &str) -> Result<HashMap<String, Valve>, ParseError> {
     let line_re: Regex =
         Regex::new(r"^Valve ([A-Z]+) has flow rate=(\d+); tunnels? leads? to valves? ([A-Z, ]+)$")
             .unwrap();
 
-    let mut valve_system = HashMap::new();
+    let mut valves = Vec::new();
     for line in input.trim_end().split('\n') {
-        let captures = line_re.captures(line).unwrap();
-        let valve_name = captures.get(1).unwrap().as_str();
-        let flow_rate = unwrap_match_to_i64(captures.get(2)) as u32;
-        let mut tunnel_to = HashMap::new();
-        captures
+        let captures = captures(input, &line_re, line)?;
+        let valve_name = captures.get(1).map_or(line, |m| m.as_str());
+        let flow_rate = capture_to_number(input, &captures, 2)?;
+        let tunnels: Vec<&str> = captures
             .get(3)
-            .unwrap()
-            .as_str()
+            .map_or("", |m| m.as_str())
             .split(", ")
-            .map(String::from)
-            .for_each(|tunnel| {
-                tunnel_to.insert(tunnel, 1);
-            });
-        valve_system.insert(
-            String::from(valve_name),
-            Valve {
-                flow_rate,
-                tunnel_to,
-            },
-        );
+            .collect();
+        valves.push((valve_name, flow_rate, tunnels));
     }
-    valve_system
+
+    let mut tunnels_of: HashMap<&str, &[&str]> = HashMap::new();
+    for (valve_name, _, tunnels) in &valves {
+        if tunnels_of.insert(valve_name, tunnels).is_some() {
+            return Err(ParseError::at(input, valve_name, "Valve is listed twice"));
+        }
+    }
+    if !tunnels_of.contains_key("AA") {
+        return Err(ParseError::at_end(
+            input,
+            "Expected a valve AA to start from",
+        ));
+    }
+    for (valve_name, _, tunnels) in &valves {
+        for tunnel in tunnels {
+            let message = match tunnels_of.get(tunnel) {
+                None => "Unknown valve",
+                Some(_) if tunnel == valve_name => "Tunnel leads back to its own valve",
+                Some(back) if !back.contains(valve_name) => "No tunnel leads back from this valve",
+                Some(_) => continue,
+            };
+            return Err(ParseError::at(input, tunnel, message));
+        }
+    }
+
+    Ok(valves
+        .into_iter()
+        .map(|(valve_name, flow_rate, tunnels)| {
+            let tunnel_to = tunnels.into_iter().map(|to| (to.to_string(), 1)).collect();
+            (
+                valve_name.to_string(),
+                Valve {
+                    flow_rate,
+                    tunnel_to,
+                },
+            )
+        })
+        .collect())
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::puzzles::assert_eq_number;
-
     use super::*;
 
-    static RAW_INPUT: &str = "Valve AA has flow rate=0; tunnels lead to valves DD, II, BB\n\
-    Valve BB has flow rate=13; tunnels lead to valves CC, AA\n\
-    Valve CC has flow rate=2; tunnels lead to valves DD, BB\n\
-    Valve DD has flow rate=20; tunnels lead to valves CC, AA, EE\n\
-    Valve EE has flow rate=3; tunnels lead to valves FF, DD\n\
-    Valve FF has flow rate=0; tunnels lead to valves EE, GG\n\
-    Valve GG has flow rate=0; tunnels lead to valves FF, HH\n\
-    Valve HH has flow rate=22; tunnel leads to valve GG\n\
-    Valve II has flow rate=0; tunnels lead to valves AA, JJ\n\
-    Valve JJ has flow rate=21; tunnel leads to valve II\n";
-
     #[test]
-    fn solves_first() {
-        assert_eq_number(1651, solve_first(String::from(RAW_INPUT)));
+    fn rejects_broken_tunnels() {
+        let error = |input: &str| Day16::parse(input).err().map(|error| error.message);
+        assert_eq!(
+            Some(String::from("Unknown valve")),
+            error("Valve AA has flow rate=0; tunnels lead to valves BB, CC\nValve BB has flow rate=3; tunnel leads to valve AA")
+        );
+        assert_eq!(
+            Some(String::from("No tunnel leads back from this valve")),
+            error("Valve AA has flow rate=0; tunnel leads to valve BB\nValve BB has flow rate=3; tunnel leads to valve CC\nValve CC has flow rate=1; tunnel leads to valve BB")
+        );
+        assert_eq!(
+            Some(String::from("Expected a valve AA to start from")),
+            error("Valve BB has flow rate=3; tunnel leads to valve CC\nValve CC has flow rate=1; tunnel leads to valve BB")
+        );
     }
 
     #[test]
-    fn solves_second() {
-        assert_eq_number(1707, solve_second(String::from(RAW_INPUT)));
+    fn ignores_unreachable_valves() {
+        let valve_system = Day16::parse(
+            "Valve AA has flow rate=0; tunnel leads to valve BB\nValve BB has flow rate=3; tunnel leads to valve AA\nValve CC has flow rate=5; tunnel leads to valve DD\nValve DD has flow rate=1; tunnel leads to valve CC",
+        )
+        .unwrap();
+        assert_eq!(Answer::from(3 * 28), Day16::part1(&valve_system));
     }
 }
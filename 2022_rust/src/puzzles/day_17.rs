@@ -1,34 +1,60 @@
 use std::collections::HashMap;
 
-use super::Result;
+use aoc_core::answer::Answer;
+use aoc_core::params::{Param, Params};
+use aoc_core::parse::ParseError;
+use aoc_core::point::Direction;
+use aoc_core::solution::Solution;
 
 const CHAMBER_WIDTH: usize = 7;
 
-enum Direction {
-    Left,
-    Right,
-}
-
 struct Shape {
     w: usize,
     h: usize,
     bitmap: Vec<Vec<bool>>,
 }
 
-pub fn solve_first(input: String) -> Result {
-    let height = simulate_rock_falling(input, 2022);
+pub struct Day17;
+
+impl Solution for Day17 {
+    const DAY: u8 = 17;
+    const TITLE: &'static str = "Pyroclastic Flow";
+    const PARAMS: &'static [Param] = &[
+        Param::new::<usize>("rocks", 2022, "Rocks to drop in the first part"),
+        Param::new::<usize>(
+            "many_rocks",
+            1_000_000_000_000,
+            "Rocks to drop in the second part",
+        ),
+    ];
+    type Input = Vec<Direction>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
 
-    Result::Number(height as u32)
-}
+    fn part1(input: &Self::Input) -> Answer {
+        Self::part1_with(input, &Params::defaults(Self::PARAMS))
+    }
+
+    fn part2(input: &Self::Input) -> Answer {
+        Self::part2_with(input, &Params::defaults(Self::PARAMS))
+    }
+
+    fn part1_with(input: &Self::Input, params: &Params) -> Answer {
+        let height = simulate_rock_falling(input, params.get("rocks"));
+
+        Answer::from(height)
+    }
 
-pub fn solve_second(input: String) -> Result {
-    let height = simulate_rock_falling(input, 1000000000000);
+    fn part2_with(input: &Self::Input, params: &Params) -> Answer {
+        let height = simulate_rock_falling(input, params.get("many_rocks"));
 
-    Result::String(height.to_string())
+        Answer::from(height)
+    }
 }
 
-fn simulate_rock_falling(input: String, rock_count: usize) -> usize {
-    let jet_pattern = parse_input(input);
+fn simulate_rock_falling(jet_pattern: &[Direction], rock_count: usize) -> usize {
     let jet_pattern_count = jet_pattern.len();
     let shapes = prepare_shapes();
     let shapes_count = shapes.len();
@@ -86,7 +112,7 @@ fn simulate_rock_falling(input: String, rock_count: usize) -> usize {
     normalized_h + chamber.len() - 1
 }
 
-fn chamber_to_u32(chamber: &Vec<Vec<bool>>) -> u32 {
+fn chamber_to_u32(chamber: &[Vec<bool>]) -> u32 {
     let mut n = 0;
     for r in chamber {
         for c in r {
@@ -99,7 +125,7 @@ fn chamber_to_u32(chamber: &Vec<Vec<bool>>) -> u32 {
     n
 }
 
-fn find_full_line(chamber: &Vec<Vec<bool>>, last_y: usize) -> Option<usize> {
+fn find_full_line(chamber: &[Vec<bool>], last_y: usize) -> Option<usize> {
     (last_y..(chamber.len())).find(|&y| chamber[y].iter().all(|b| *b))
 }
 
@@ -116,21 +142,14 @@ fn add_rock(chamber: &mut Vec<Vec<bool>>, shape: &Shape, (new_x, new_y): (usize,
 }
 
 fn try_jet_push(
-    chamber: &Vec<Vec<bool>>,
+    chamber: &[Vec<bool>],
     shape: &Shape,
     (cur_x, cur_y): (usize, usize),
     jet_push: &Direction,
 ) -> (usize, usize) {
-    let next_x = match jet_push {
-        Direction::Left => cur_x.checked_sub(1),
-        Direction::Right => {
-            if cur_x + shape.w < CHAMBER_WIDTH {
-                Some(cur_x + 1)
-            } else {
-                None
-            }
-        }
-    };
+    let next_x = cur_x
+        .checked_add_signed(jet_push.offset().0)
+        .filter(|next_x| next_x + shape.w <= CHAMBER_WIDTH);
 
     if let Some(next_x) = next_x {
         if is_overlapping(chamber, shape, (next_x, cur_y)) {
@@ -143,7 +162,7 @@ fn try_jet_push(
     }
 }
 
-fn is_overlapping(chamber: &Vec<Vec<bool>>, shape: &Shape, (new_x, new_y): (usize, usize)) -> bool {
+fn is_overlapping(chamber: &[Vec<bool>], shape: &Shape, (new_x, new_y): (usize, usize)) -> bool {
     for y in 0..shape.h {
         if y + new_y < chamber.len() {
             for x in 0..shape.w {
@@ -196,35 +215,32 @@ fn prepare_shapes() -> Vec<Shape> {
     vec![shape_1, shape_2, shape_3, shape_4, shape_5]
 }
 
-fn parse_input(input: String) -> Vec<Direction> {
+fn parse_input(input: &str) -> Result<Vec<Direction>, ParseError> {
     input
         .trim_end()
-        .as_bytes()
-        .iter()
-        .map(|c| match c {
-            b'<' => Direction::Left,
-            b'>' => Direction::Right,
-            _ => panic!("Unexpected character"),
+        .char_indices()
+        .map(|(i, c)| {
+            Direction::from_char(c)
+                .filter(|_| matches!(c, '<' | '>'))
+                .ok_or_else(|| {
+                    ParseError::at(input, &input[i..i + c.len_utf8()], "Unexpected character")
+                })
         })
         .collect()
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::input_utils::get_input;
-    use crate::puzzles::assert_eq_number;
-
     use super::*;
 
-    static RAW_INPUT: &str = ">>><<><>><<<>><>>><<<>>><<<><<<>><>><<>>\n";
-
-    #[test]
-    fn solves_first() {
-        assert_eq_number(3068, solve_first(String::from(RAW_INPUT)));
-    }
-
     #[test]
     fn solves_for_100000() {
-        assert_eq!(155657, simulate_rock_falling(get_input(17), 100000));
+        assert_eq!(
+            155657,
+            simulate_rock_falling(
+                &Day17::parse(include_str!("../../resources/day_17_input.txt")).unwrap(),
+                100000
+            )
+        );
     }
 }
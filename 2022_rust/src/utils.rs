@@ -1,11 +1,18 @@
-pub fn unwrap_match_to_usize(re_match: Option<regex::Match<'_>>) -> usize {
-    re_match
-        .unwrap()
-        .as_str()
-        .parse()
-        .expect("Expected a number here")
+use std::str::FromStr;
+
+use regex::{Captures, Regex};
+
+use aoc_core::parse::{self, ParseError};
+
+pub fn captures<'a>(input: &str, re: &Regex, line: &'a str) -> Result<Captures<'a>, ParseError> {
+    re.captures(line)
+        .ok_or_else(|| ParseError::at(input, line, "Input line is not of expected format"))
 }
 
-pub fn unwrap_match_to_i64(re_match: Option<regex::Match<'_>>) -> i64 {
-    re_match.unwrap().as_str().parse().unwrap()
+pub fn capture_to_number<T: FromStr>(
+    input: &str,
+    captures: &Captures<'_>,
+    group: usize,
+) -> Result<T, ParseError> {
+    parse::number(input, &captures[group])
 }
@@ -9,39 +9,25 @@ mod day_08;
 mod day_09;
 mod day_11;
 
-#[derive(Debug, PartialEq)]
-pub enum Result {
-    Number(u64),
-    String(String),
-}
-
-fn panic_not_implemented(_: String) -> Result {
-    panic!("Not implemented")
-}
+use aoc_core::solution::Puzzles;
 
-type Puzzle = (fn(String) -> Result, fn(String) -> Result);
-
-pub fn get_all_puzzles() -> [Puzzle; 12] {
-    [
-        (day_01::solve_first, day_01::solve_second),
-        (day_02::solve_first, day_02::solve_second),
-        (day_03::solve_first, day_03::solve_second),
-        (day_04::solve_first, day_04::solve_second),
-        (day_05::solve_first, day_05::solve_second),
-        (day_06::solve_first, day_06::solve_second),
-        (day_07::solve_first, day_07::solve_second),
-        (day_08::solve_first, day_08::solve_second),
-        (day_09::solve_first, day_09::solve_second),
-        (panic_not_implemented, panic_not_implemented),
-        (day_11::solve_first, day_11::solve_second),
-        (panic_not_implemented, panic_not_implemented),
+pub fn get_all_puzzles() -> Puzzles {
+    aoc_core::puzzles![
+        2025;
+        day_01::Day01,
+        day_02::Day02,
+        day_03::Day03,
+        day_04::Day04,
+        day_05::Day05,
+        day_06::Day06,
+        day_07::Day07,
+        day_08::Day08,
+        day_09::Day09,
+        day_11::Day11,
     ]
 }
 
-pub fn assert_eq_number(expected: u64, actual: Result) {
-    assert_eq!(Result::Number(expected), actual)
-}
-
-pub fn assert_eq_string(expected: String, actual: Result) {
-    assert_eq!(Result::String(expected), actual)
+#[cfg(test)]
+mod examples {
+    include!(concat!(env!("OUT_DIR"), "/examples.rs"));
 }
@@ -1,30 +1,44 @@
-use super::Result;
+use aoc_core::answer::Answer;
+use aoc_core::parse::{self, ParseError};
+use aoc_core::solution::Solution;
 
-pub fn solve_first(input: String) -> Result {
-    let mut at_zero = 0_u64;
-    let mut orientation = 50;
-    for raw_rotation in input.lines() {
-        let rotation = parse_line(raw_rotation);
-        orientation = match rotation {
-            Some(('L', l)) => orientation - l,
-            Some(('R', r)) => orientation + r,
-            _ => orientation,
-        };
-        orientation %= 100;
-        if orientation == 0 {
-            at_zero += 1;
+pub struct Day01;
+
+impl Solution for Day01 {
+    const DAY: u8 = 1;
+    const TITLE: &'static str = "Secret Entrance";
+    type Input = Vec<(char, i32)>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        input
+            .lines()
+            .filter(|line| !line.is_empty())
+            .map(|line| parse_line(input, line))
+            .collect()
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        let mut at_zero = 0_u64;
+        let mut orientation = 50;
+        for &rotation in input {
+            orientation = match rotation {
+                ('L', l) => orientation - l,
+                ('R', r) => orientation + r,
+                _ => orientation,
+            };
+            orientation %= 100;
+            if orientation == 0 {
+                at_zero += 1;
+            }
         }
+        Answer::from(at_zero)
     }
-    Result::Number(at_zero)
-}
 
-pub fn solve_second(input: String) -> Result {
-    let mut at_zero = 0;
-    let mut orientation = 50;
-    for raw_rotation in input.lines() {
-        let rotation = parse_line(raw_rotation);
-        orientation = match rotation {
-            Some((c, t)) => {
+    fn part2(input: &Self::Input) -> Answer {
+        let mut at_zero = 0;
+        let mut orientation = 50;
+        for &(c, t) in input {
+            orientation = {
                 at_zero += (t / 100) as u32;
                 let n = t % 100;
                 match c {
@@ -42,47 +56,21 @@ pub fn solve_second(input: String) -> Result {
                     }
                     _ => orientation,
                 }
-            }
-            _ => orientation,
-        };
-        orientation = orientation.rem_euclid(100);
-    }
-    Result::Number(at_zero.into())
-}
-
-fn parse_line(s: &str) -> Option<(char, i32)> {
-    let first = s.chars().next()?;
-    if first != 'L' && first != 'R' {
-        return None;
+            };
+            orientation = orientation.rem_euclid(100);
+        }
+        Answer::from(at_zero)
     }
-    let num = s[1..].parse().ok()?;
-    Some((first, num))
 }
 
-#[cfg(test)]
-mod tests {
-    use crate::puzzles::assert_eq_number;
-
-    use super::*;
-
-    static RAW_INPUT: &str = "L68\n\
-    L30\n\
-    R48\n\
-    L5\n\
-    R60\n\
-    L55\n\
-    L1\n\
-    L99\n\
-    R14\n\
-    L82\n";
-
-    #[test]
-    fn solves_first() {
-        assert_eq_number(3, solve_first(String::from(RAW_INPUT)));
-    }
-
-    #[test]
-    fn solves_second() {
-        assert_eq_number(6, solve_second(String::from(RAW_INPUT)));
+fn parse_line(input: &str, s: &str) -> Result<(char, i32), ParseError> {
+    if !s.starts_with(['L', 'R']) {
+        return Err(ParseError::at(
+            input,
+            s,
+            "Rotation should start with L or R",
+        ));
     }
+    let num = parse::number(input, &s[1..])?;
+    Ok((s.as_bytes()[0] as char, num))
 }
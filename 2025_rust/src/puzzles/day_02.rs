@@ -1,35 +1,42 @@
-use super::Result;
-use std::collections::{BTreeMap, HashSet};
+use aoc_core::answer::Answer;
+use aoc_core::interval::IntervalSet;
+use aoc_core::parse::{self, ParseError};
+use aoc_core::solution::Solution;
+use std::collections::HashSet;
 
-pub fn solve_first(input: String) -> Result {
-    solve_with(input, generate_invalid_ids)
-}
+pub struct Day02;
 
-pub fn solve_second(input: String) -> Result {
-    solve_with(input, generate_invalid_ids_part2)
-}
+impl Solution for Day02 {
+    const DAY: u8 = 2;
+    const TITLE: &'static str = "Gift Shop";
+    type Input = IntervalSet<u64>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_ranges(input)
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        solve_with(input, generate_invalid_ids)
+    }
 
-fn solve_with(input: String, generator: impl Fn(u64) -> HashSet<u64>) -> Result {
-    let ranges = parse_ranges(&input);
+    fn part2(input: &Self::Input) -> Answer {
+        solve_with(input, generate_invalid_ids_part2)
+    }
+}
 
-    let Some((_, max)) = ranges.last_key_value() else {
-        return Result::Number(0);
+fn solve_with(ranges: &IntervalSet<u64>, generator: impl Fn(u64) -> HashSet<u64>) -> Answer {
+    let Some(last) = ranges.iter().next_back() else {
+        return Answer::from(0);
     };
 
-    let invalid_ids = generator(*max);
+    let invalid_ids = generator(*last.end());
 
     let sum: u64 = invalid_ids
         .into_iter()
-        .filter(|&id| {
-            ranges
-                .range(..=id)
-                .next_back()
-                .map(|(_, up)| id <= *up)
-                .unwrap_or(false)
-        })
+        .filter(|&id| ranges.contains(id))
         .sum();
 
-    Result::Number(sum)
+    Answer::from(sum)
 }
 
 fn init_invalid_id_state() -> (u64, u64, u64) {
@@ -80,38 +87,13 @@ fn generate_invalid_ids_part2(max: u64) -> HashSet<u64> {
     invalid_ids
 }
 
-fn parse_ranges(input: &str) -> BTreeMap<u64, u64> {
+fn parse_ranges(input: &str) -> Result<IntervalSet<u64>, ParseError> {
     input
         .trim()
         .split(',')
-        .filter_map(|raw| parse_range(raw).map(|(_, lo, hi)| (lo, hi)))
+        .map(|raw| {
+            let (lo, hi) = parse::split_once(input, raw, "-")?;
+            Ok(parse::number(input, lo)?..=parse::number(input, hi)?)
+        })
         .collect()
 }
-
-fn parse_range(s: &str) -> Option<(u32, u64, u64)> {
-    let (l, u) = s.split_once('-')?;
-    Some((l.len() as u32, l.parse().ok()?, u.parse().ok()?))
-}
-
-#[cfg(test)]
-mod tests {
-    use crate::puzzles::assert_eq_number;
-
-    use super::*;
-
-    static RAW_INPUT: &str = "\
-    11-22,95-115,998-1012,1188511880-1188511890,\
-    222220-222224,1698522-1698528,446443-446449,\
-    38593856-38593862,565653-565659,\
-    824824821-824824827,2121212118-2121212124";
-
-    #[test]
-    fn solves_first() {
-        assert_eq_number(1227775554, solve_first(String::from(RAW_INPUT)));
-    }
-
-    #[test]
-    fn solves_second() {
-        assert_eq_number(4174379265, solve_second(String::from(RAW_INPUT)));
-    }
-}
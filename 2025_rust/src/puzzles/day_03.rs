@@ -1,26 +1,46 @@
-use super::Result;
+use aoc_core::answer::Answer;
+use aoc_core::parse::ParseError;
+use aoc_core::solution::Solution;
 
 const MIN_DIGIT: u8 = b'0';
 const MAX_DIGIT: u8 = b'9';
 
-pub fn solve_first(input: String) -> Result {
-    solve(input, 2)
-}
+pub struct Day03;
+
+impl Solution for Day03 {
+    const DAY: u8 = 3;
+    const TITLE: &'static str = "Lobby";
+    type Input = Vec<Vec<u8>>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        input
+            .lines()
+            .map(|line| match line.find(|c: char| !c.is_ascii_digit()) {
+                Some(i) => Err(ParseError::at(
+                    input,
+                    &line[i..],
+                    "Expected a joltage rating",
+                )),
+                None => Ok(line.as_bytes().to_vec()),
+            })
+            .collect()
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        solve(input, 2)
+    }
 
-pub fn solve_second(input: String) -> Result {
-    solve(input, 12)
+    fn part2(input: &Self::Input) -> Answer {
+        solve(input, 12)
+    }
 }
 
-fn solve(input: String, size: usize) -> Result {
-    let joltage = input
-        .lines()
-        .map(|line| calculate_joltage(line, size))
-        .sum();
-    Result::Number(joltage)
+fn solve(banks: &[Vec<u8>], size: usize) -> Answer {
+    let joltage: u64 = banks.iter().map(|bank| calculate_joltage(bank, size)).sum();
+    Answer::from(joltage)
 }
 
-fn calculate_joltage(line: &str, size: usize) -> u64 {
-    let bytes = line.as_bytes();
+fn calculate_joltage(bytes: &[u8], size: usize) -> u64 {
     let mut joltage = 0_u64;
     let mut i;
     let mut max;
@@ -40,26 +60,3 @@ fn calculate_joltage(line: &str, size: usize) -> u64 {
 
     joltage
 }
-
-#[cfg(test)]
-mod tests {
-    use crate::puzzles::assert_eq_number;
-
-    use super::*;
-
-    static RAW_INPUT: &str = "\
-    987654321111111\n\
-    811111111111119\n\
-    234234234234278\n\
-    818181911112111\n";
-
-    #[test]
-    fn solves_first() {
-        assert_eq_number(357, solve_first(String::from(RAW_INPUT)));
-    }
-
-    #[test]
-    fn solves_second() {
-        assert_eq_number(3121910778619, solve_second(String::from(RAW_INPUT)));
-    }
-}
@@ -1,159 +1,96 @@
-use super::Result;
-
-const NEIGHBORS: [(isize, isize); 8] = [
-    (-1, -1),
-    (-1, 0),
-    (-1, 1),
-    (0, -1),
-    (0, 1),
-    (1, -1),
-    (1, 0),
-    (1, 1),
-];
-
-#[derive(PartialEq)]
-enum GridValue {
+use aoc_core::answer::Answer;
+use aoc_core::grid::Grid;
+use aoc_core::parse::ParseError;
+use aoc_core::point::Point2;
+use aoc_core::solution::Solution;
+
+#[derive(Clone, PartialEq)]
+pub enum GridValue {
     Roll,
     Empty,
 }
 
-pub fn solve_first(input: String) -> Result {
-    solve(input, true)
-}
+pub struct Day04;
 
-pub fn solve_second(input: String) -> Result {
-    solve(input, false)
-}
+impl Solution for Day04 {
+    const DAY: u8 = 4;
+    const TITLE: &'static str = "Printing Department";
+    type Input = Grid<GridValue>;
 
-fn solve(input: String, only_first: bool) -> Result {
-    let mut grid = parse_input(&input);
-    let Some(row) = grid.first() else {
-        return Result::Number(0);
-    };
-    let cols = row.len();
-    let rows = grid.len();
-    let mut neighbors = calculate_neighbors(rows, cols, &grid);
-    let mut stack: Vec<(usize, usize)> = Vec::new();
-    for (y, row) in neighbors.iter().enumerate() {
-        for (x, count) in row.iter().enumerate() {
-            if *count < 4 {
-                stack.push((y, x));
-            }
-        }
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        solve(input, true)
     }
+
+    fn part2(input: &Self::Input) -> Answer {
+        solve(input, false)
+    }
+}
+
+fn solve(grid: &Grid<GridValue>, only_first: bool) -> Answer {
+    let mut grid = grid.clone();
+    let mut neighbors = calculate_neighbors(&grid);
+    let mut stack: Vec<Point2<usize>> = neighbors
+        .iter()
+        .filter(|(_, count)| **count < 4)
+        .map(|(position, _)| position)
+        .collect();
     if only_first {
-        Result::Number(stack.len() as u64)
+        Answer::from(stack.len())
     } else {
         let mut rolls = 0;
-        while let Some((y, x)) = stack.pop() {
-            if grid[y][x] == GridValue::Roll {
+        while let Some(position) = stack.pop() {
+            if grid[position] == GridValue::Roll {
                 rolls += 1;
-                process_roll(y, x, &mut grid, &mut neighbors, &mut stack);
+                process_roll(position, &mut grid, &mut neighbors, &mut stack);
             }
         }
-        Result::Number(rolls)
+        Answer::from(rolls)
     }
 }
 
 fn process_roll(
-    y: usize,
-    x: usize,
-    grid: &mut [Vec<GridValue>],
-    neighbors: &mut [Vec<u32>],
-    stack: &mut Vec<(usize, usize)>,
+    position: Point2<usize>,
+    grid: &mut Grid<GridValue>,
+    neighbors: &mut Grid<u32>,
+    stack: &mut Vec<Point2<usize>>,
 ) {
-    grid[y][x] = GridValue::Empty;
+    grid[position] = GridValue::Empty;
 
-    let rows = grid.len();
-    let cols = grid[0].len();
-
-    for (ny, nx) in neighbors_of(y, x, rows, cols) {
-        if grid[ny][nx] == GridValue::Roll {
-            neighbors[ny][nx] -= 1;
-            if neighbors[ny][nx] < 4 {
-                stack.push((ny, nx));
+    for neighbor in grid.neighbors8(position) {
+        if grid[neighbor] == GridValue::Roll {
+            neighbors[neighbor] -= 1;
+            if neighbors[neighbor] < 4 {
+                stack.push(neighbor);
             }
         }
     }
 }
 
-fn calculate_neighbors(rows: usize, cols: usize, grid: &[Vec<GridValue>]) -> Vec<Vec<u32>> {
-    let mut neighbors: Vec<Vec<u32>> = vec![vec![0; cols]; rows];
-    for y in 0..rows {
-        for x in 0..cols {
-            if grid[y][x] == GridValue::Empty {
-                neighbors[y][x] = 8;
-                continue;
-            }
-            for (ny, nx) in neighbors_of(y, x, rows, cols) {
-                if grid[ny][nx] == GridValue::Roll {
-                    neighbors[y][x] += 1;
-                }
-            }
-        }
+/// Number of rolls around every roll, empty cells count as surrounded
+fn calculate_neighbors(grid: &Grid<GridValue>) -> Grid<u32> {
+    let mut neighbors = Grid::new(grid.width(), grid.height(), 0);
+    for (position, value) in grid.iter() {
+        neighbors[position] = match value {
+            GridValue::Empty => 8,
+            GridValue::Roll => grid
+                .neighbors8(position)
+                .filter(|&neighbor| grid[neighbor] == GridValue::Roll)
+                .count() as u32,
+        };
     }
     neighbors
 }
 
-fn neighbors_of(
-    y: usize,
-    x: usize,
-    rows: usize,
-    cols: usize,
-) -> impl Iterator<Item = (usize, usize)> {
-    NEIGHBORS.into_iter().filter_map(move |(dy, dx)| {
-        let ny = y as isize + dy;
-        let nx = x as isize + dx;
-        if ny >= 0 && nx >= 0 && ny < rows as isize && nx < cols as isize {
-            Some((ny as usize, nx as usize))
+fn parse_input(input: &str) -> Result<Grid<GridValue>, ParseError> {
+    Grid::parse(input, |c| {
+        Some(if c == '@' {
+            GridValue::Roll
         } else {
-            None
-        }
-    })
-}
-
-fn parse_input(input: &str) -> Vec<Vec<GridValue>> {
-    input
-        .lines()
-        .map(|l| {
-            l.chars()
-                .map(|c| {
-                    if c == '@' {
-                        GridValue::Roll
-                    } else {
-                        GridValue::Empty
-                    }
-                })
-                .collect()
+            GridValue::Empty
         })
-        .collect()
-}
-
-#[cfg(test)]
-mod tests {
-    use crate::puzzles::assert_eq_number;
-
-    use super::*;
-
-    static RAW_INPUT: &str = "\
-    ..@@.@@@@.\n\
-    @@@.@.@.@@\n\
-    @@@@@.@.@@\n\
-    @.@@@@..@.\n\
-    @@.@@@@.@@\n\
-    .@@@@@@@.@\n\
-    .@.@.@.@@@\n\
-    @.@@@.@@@@\n\
-    .@@@@@@@@.\n\
-    @.@.@@@.@.\n";
-
-    #[test]
-    fn solves_first() {
-        assert_eq_number(13, solve_first(String::from(RAW_INPUT)));
-    }
-
-    #[test]
-    fn solves_second() {
-        assert_eq_number(43, solve_second(String::from(RAW_INPUT)));
-    }
+    })
 }
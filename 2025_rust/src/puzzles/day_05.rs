@@ -1,30 +1,33 @@
-use super::Result;
-use std::collections::BTreeMap;
-use std::ops::Bound::Included;
+use aoc_core::answer::Answer;
+use aoc_core::interval::IntervalSet;
+use aoc_core::parse::{self, ParseError};
+use aoc_core::solution::Solution;
 
-pub fn solve_first(input: String) -> Result {
-    let (fresh, available) = parse_input(&input);
-    let fresh_available = available
-        .into_iter()
-        .filter(|&a| {
-            fresh
-                .range(..=a)
-                .next_back()
-                .map(|(_, top)| a <= *top)
-                .unwrap_or(false)
-        })
-        .count();
-    Result::Number(fresh_available as u64)
-}
+pub struct Day05;
+
+impl Solution for Day05 {
+    const DAY: u8 = 5;
+    const TITLE: &'static str = "Cafeteria";
+    type Input = (IntervalSet<u64>, Vec<u64>);
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        let (fresh, available) = input;
+        let fresh_available = available.iter().filter(|&&a| fresh.contains(a)).count();
+        Answer::from(fresh_available)
+    }
 
-pub fn solve_second(input: String) -> Result {
-    let (fresh, _) = parse_input(&input);
-    let all_fresh = fresh.iter().map(|(start, end)| end + 1 - start).sum();
-    Result::Number(all_fresh)
+    fn part2(input: &Self::Input) -> Answer {
+        let (fresh, _) = input;
+        Answer::from(fresh.total_len())
+    }
 }
 
-fn parse_input(input: &str) -> (BTreeMap<u64, u64>, Vec<u64>) {
-    let mut fresh = BTreeMap::new();
+fn parse_input(input: &str) -> Result<(IntervalSet<u64>, Vec<u64>), ParseError> {
+    let mut fresh = IntervalSet::new();
     let mut available = Vec::new();
     for line in input.lines() {
         if line.trim().is_empty() {
@@ -32,75 +35,26 @@ fn parse_input(input: &str) -> (BTreeMap<u64, u64>, Vec<u64>) {
         }
 
         if let Some((left, right)) = line.split_once('-') {
-            let start = left.parse().ok().unwrap();
-            let end = right.parse().ok().unwrap();
-            add_range(&mut fresh, start, end);
+            let start = parse::number(input, left)?;
+            let end = parse::number(input, right)?;
+            fresh.insert(start..=end);
         } else {
-            let i = line.parse().ok().unwrap();
+            let i = parse::number(input, line)?;
             available.push(i);
         }
     }
-    (fresh, available)
-}
-
-fn add_range(fresh: &mut BTreeMap<u64, u64>, start: u64, end: u64) {
-    let mut new_start = start;
-    let mut new_end = end;
-    if let Some((&s, &e)) = fresh.range(..=start).next_back()
-        && start <= e
-    {
-        if new_start > s {
-            new_start = s;
-        }
-        if new_end < e {
-            new_end = e;
-        }
-        fresh.remove(&s);
-    }
-
-    let ranges: Vec<(u64, u64)> = fresh
-        .range((Included(start), Included(end)))
-        .map(|(k, v)| (*k, *v))
-        .collect();
-
-    for (s, e) in ranges {
-        if new_start > s {
-            new_start = s;
-        }
-        if new_end < e {
-            new_end = e;
-        }
-        fresh.remove(&s);
-    }
-    fresh.insert(new_start, new_end);
+    Ok((fresh, available))
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::puzzles::assert_eq_number;
-
     use super::*;
 
-    static RAW_INPUT: &str = "\
-        3-5\n\
-        10-14\n\
-        16-20\n\
-        12-18\n\
-        \n\
-        1\n\
-        5\n\
-        8\n\
-        11\n\
-        17\n\
-        32";
-
-    #[test]
-    fn solves_first() {
-        assert_eq_number(3, solve_first(String::from(RAW_INPUT)));
-    }
-
     #[test]
-    fn solves_second() {
-        assert_eq_number(14, solve_second(String::from(RAW_INPUT)));
+    fn parses_input() {
+        let (fresh, available) =
+            Day05::parse(include_str!("../../resources/day_05_input_test.txt")).unwrap();
+        assert_eq!(vec![3..=5, 10..=20], fresh.iter().collect::<Vec<_>>());
+        assert_eq!(vec![1, 5, 8, 11, 17, 32], available);
     }
 }
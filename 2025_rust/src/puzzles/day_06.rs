@@ -1,130 +1,175 @@
-use super::Result;
+use aoc_core::answer::Answer;
+use aoc_core::parse::ParseError;
+use aoc_core::solution::Solution;
 
-#[derive(PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 enum Operator {
     Add,
     Multiply,
 }
 
-pub fn solve_first(input: String) -> Result {
-    let (values, operators) = parse_input_first(&input);
-    solve(values, operators)
+impl Operator {
+    fn apply(self, numbers: impl Iterator<Item = u64>) -> u64 {
+        match self {
+            Operator::Add => numbers.sum(),
+            Operator::Multiply => numbers.product(),
+        }
+    }
+}
+
+/// A problem of the worksheet, the parts disagree on how to read its digits
+struct Problem {
+    operator: Operator,
+    /// The digit of every line in every column of the problem, `None` where it is blank
+    columns: Vec<Vec<Option<u8>>>,
 }
 
-pub fn solve_second(input: String) -> Result {
-    let (values, operators) = parse_input_second(&input);
-    solve(values, operators)
+pub struct Worksheet {
+    problems: Vec<Problem>,
 }
 
-fn solve(values: Vec<Vec<u64>>, operators: Vec<Operator>) -> Result {
-    let s = values
+pub struct Day06;
+
+impl Solution for Day06 {
+    const DAY: u8 = 6;
+    const TITLE: &'static str = "Trash Compactor";
+    type Input = Worksheet;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        solve(input, read_by_rows)
+    }
+
+    fn part2(input: &Self::Input) -> Answer {
+        solve(input, read_by_columns)
+    }
+}
+
+fn solve(worksheet: &Worksheet, read: fn(&Problem) -> Vec<u64>) -> Answer {
+    let s: u64 = worksheet
+        .problems
         .iter()
-        .zip(&operators)
-        .map(|(v, o)| match o {
-            Operator::Add => v.iter().sum::<u64>(),
-            Operator::Multiply => v.iter().product(),
-        })
+        .map(|problem| problem.operator.apply(read(problem).into_iter()))
         .sum();
-    Result::Number(s)
+    Answer::from(s)
 }
 
-fn parse_input_first(input: &str) -> (Vec<Vec<u64>>, Vec<Operator>) {
-    let mut values: Vec<Vec<u64>> = Vec::new();
-    let mut operators = Vec::new();
-    for line in input.lines() {
-        if line.starts_with('*') || line.starts_with('+') {
-            operators = parse_operators(line);
-        } else {
-            for (i, &v) in parse_numbers(line).iter().enumerate() {
-                if let Some(vs) = values.get_mut(i) {
-                    vs.push(v);
-                } else {
-                    let vs = vec![v];
-                    values.push(vs);
-                }
-            }
-        }
-    }
-    (values, operators)
+/// Every line of the problem is a number
+fn read_by_rows(problem: &Problem) -> Vec<u64> {
+    let rows = problem.columns.first().map_or(0, Vec::len);
+    (0..rows)
+        .filter_map(|row| number(problem.columns.iter().map(|column| column[row])))
+        .collect()
 }
 
-fn parse_input_second(input: &str) -> (Vec<Vec<u64>>, Vec<Operator>) {
-    let mut values: Vec<Vec<u64>> = Vec::new();
-    let mut operators = Vec::new();
+/// Every column of the problem is a number, read from top to bottom
+fn read_by_columns(problem: &Problem) -> Vec<u64> {
+    problem
+        .columns
+        .iter()
+        .filter_map(|column| number(column.iter().copied()))
+        .collect()
+}
+
+/// The number written by the digits with the blanks left out, `None` without any digit
+fn number(digits: impl Iterator<Item = Option<u8>>) -> Option<u64> {
+    digits.flatten().fold(None, |n, digit| {
+        Some(n.unwrap_or(0) * 10 + u64::from(digit))
+    })
+}
+
+/// Every problem starts at its operator and ends before the blank column
+/// preceding the next one
+fn parse_input(input: &str) -> Result<Worksheet, ParseError> {
+    let mut number_lines: Vec<&str> = input.trim_end_matches(['\n', '\r']).lines().collect();
+    let operator_line = number_lines
+        .pop()
+        .filter(|line| !line.trim().is_empty())
+        .ok_or_else(|| ParseError::at_end(input, "Expected a line of operators"))?;
 
-    let mut raw_number_lines = Vec::new();
-    let mut raw_operators: &[u8] = b"";
-    for line in input.lines() {
-        if line.starts_with('*') || line.starts_with('+') {
-            raw_operators = line.as_bytes();
-        } else {
-            raw_number_lines.push(line.as_bytes());
+    let mut digits: Vec<Vec<Option<u8>>> = Vec::new();
+    for line in &number_lines {
+        if let Some(i) = line.find(|c: char| c != ' ' && !c.is_ascii_digit()) {
+            return Err(ParseError::at(input, &line[i..], "Expected a number"));
         }
+        digits.push(
+            line.bytes()
+                .map(|b| b.is_ascii_digit().then(|| b - b'0'))
+                .collect(),
+        );
     }
-    let mut i = 0;
-    let mut start_i;
-    while i < raw_operators.len() {
-        if b'+' == raw_operators[i] {
-            operators.push(Operator::Add);
-        } else {
-            operators.push(Operator::Multiply);
-        }
-        start_i = i;
-        i += 1;
-        while i < raw_operators.len() && b' ' == raw_operators[i] {
-            i += 1;
-        }
-        let end_i = if i < raw_operators.len() { i - 1 } else { i };
-        let mut numbers = Vec::new();
-        for j in start_i..end_i {
-            let mut n = 0_u64;
-            for raw_numbers in raw_number_lines.iter() {
-                if b' ' != raw_numbers[j] {
-                    n = n * 10 + u64::from(raw_numbers[j] - b'0');
-                }
+
+    let mut starts = Vec::new();
+    let mut operators = Vec::new();
+    for token in operator_line.split(' ').filter(|token| !token.is_empty()) {
+        operators.push(match token {
+            "+" => Operator::Add,
+            "*" => Operator::Multiply,
+            _ => return Err(ParseError::at(input, token, "Unknown operator")),
+        });
+        starts.push(token.as_ptr() as usize - operator_line.as_ptr() as usize);
+    }
+
+    let blanks = (0..starts[0]).chain(starts[1..].iter().map(|start| start - 1));
+    for column in blanks {
+        for (line, line_digits) in number_lines.iter().zip(&digits) {
+            if line_digits.get(column).copied().flatten().is_some() {
+                return Err(ParseError::at(
+                    input,
+                    &line[column..=column],
+                    "Expected a blank column between the problems",
+                ));
             }
-            numbers.push(n);
         }
-        values.push(numbers);
     }
-    (values, operators)
-}
 
-fn parse_numbers(input: &str) -> Vec<u64> {
-    input
-        .split_whitespace()
-        .map(|s| s.parse::<u64>().unwrap())
-        .collect()
-}
-
-fn parse_operators(input: &str) -> Vec<Operator> {
-    input
-        .split_whitespace()
-        .map(|s| {
-            if s == "+" {
-                Operator::Add
-            } else {
-                Operator::Multiply
-            }
+    let width = digits
+        .iter()
+        .map(Vec::len)
+        .chain([operator_line.len()])
+        .max()
+        .unwrap_or(0);
+    let problems = starts
+        .iter()
+        .zip(operators)
+        .enumerate()
+        .map(|(i, (&start, operator))| {
+            let end = starts.get(i + 1).map_or(width, |next| next - 1);
+            let columns = (start..end)
+                .map(|column| {
+                    digits
+                        .iter()
+                        .map(|line| line.get(column).copied().flatten())
+                        .collect()
+                })
+                .collect();
+            Problem { operator, columns }
         })
-        .collect()
+        .collect();
+    Ok(Worksheet { problems })
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::puzzles::assert_eq_number;
-
     use super::*;
 
-    static RAW_INPUT: &str = "123 328  51 64 \n 45 64  387 23 \n  6 98  215 314\n*   +   *   +  ";
-
     #[test]
-    fn solves_first() {
-        assert_eq_number(4277556, solve_first(String::from(RAW_INPUT)));
+    fn rejects_unknown_operators() {
+        let error = Day06::parse("12 3\n 4 5\n*  -\n").err().unwrap();
+        assert_eq!((3, 4), (error.line, error.column));
+        assert_eq!("Unknown operator", error.message);
     }
 
     #[test]
-    fn solves_second() {
-        assert_eq_number(3263827, solve_second(String::from(RAW_INPUT)));
+    fn rejects_misaligned_numbers() {
+        let error = Day06::parse("12  4\n 45 6\n*  +\n").err().unwrap();
+        assert_eq!((2, 3), (error.line, error.column));
+        assert_eq!(
+            "Expected a blank column between the problems",
+            error.message
+        );
     }
 }
@@ -1,128 +1,97 @@
-use super::Result;
+use aoc_core::answer::Answer;
+use aoc_core::grid::Grid;
+use aoc_core::parse::ParseError;
+use aoc_core::point::{Direction, Point2};
+use aoc_core::solution::Solution;
 use std::collections::HashMap;
 
-#[derive(PartialEq)]
-enum GridValue {
+#[derive(Clone, PartialEq)]
+pub enum GridValue {
     Beam,
     Splitter,
     Empty,
 }
 
-pub fn solve_first(input: String) -> Result {
-    let mut grid = parse_input(&input);
-    let start = grid[0].iter().position(|v| *v == GridValue::Beam).unwrap();
-    let mut beams = vec![(start, 0_usize)];
-    let mut splits = 0;
-    while let Some((x, y)) = beams.pop() {
-        if y + 1 < grid.len() {
-            match grid[y + 1][x] {
+pub struct Day07;
+
+impl Solution for Day07 {
+    const DAY: u8 = 7;
+    const TITLE: &'static str = "Laboratories";
+    type Input = Grid<GridValue>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Answer {
+        let mut grid = input.clone();
+        let start = grid.find(&GridValue::Beam).unwrap();
+        let mut beams = vec![start];
+        let mut splits = 0;
+        while let Some(beam) = beams.pop() {
+            let Some(below) = grid.offset(beam, Direction::Down) else {
+                continue;
+            };
+            match grid[below] {
                 GridValue::Splitter => {
                     splits += 1;
-                    if x > 0 && grid[y + 1][x - 1] != GridValue::Beam {
-                        grid[y + 1][x - 1] = GridValue::Beam;
-                        beams.push((x - 1, y + 1));
-                    }
-                    if x + 1 < grid[0].len() && grid[y + 1][x + 1] != GridValue::Beam {
-                        grid[y + 1][x + 1] = GridValue::Beam;
-                        beams.push((x + 1, y + 1));
+                    for side in [Direction::Left, Direction::Right] {
+                        if let Some(split) = grid.offset(below, side)
+                            && grid[split] != GridValue::Beam
+                        {
+                            grid[split] = GridValue::Beam;
+                            beams.push(split);
+                        }
                     }
                 }
                 GridValue::Empty => {
-                    grid[y + 1][x] = GridValue::Beam;
-                    beams.push((x, y + 1));
+                    grid[below] = GridValue::Beam;
+                    beams.push(below);
                 }
                 GridValue::Beam => {}
             }
         }
+        Answer::from(splits)
     }
-    Result::Number(splits)
-}
 
-pub fn solve_second(input: String) -> Result {
-    let grid = parse_input(&input);
-    let mut cache_timelines: HashMap<(usize, usize), u64> = HashMap::new();
-    let start = grid[0].iter().position(|v| *v == GridValue::Beam).unwrap();
-    let timelines = get_timelines((start, 0_usize), &grid, &mut cache_timelines);
-    Result::Number(timelines)
+    fn part2(input: &Self::Input) -> Answer {
+        let grid = input;
+        let mut cache_timelines: HashMap<Point2<usize>, u64> = HashMap::new();
+        let start = grid.find(&GridValue::Beam).unwrap();
+        let timelines = get_timelines(start, grid, &mut cache_timelines);
+        Answer::from(timelines)
+    }
 }
 
 fn get_timelines(
-    (x, y): (usize, usize),
-    grid: &Vec<Vec<GridValue>>,
-    cache_timelines: &mut HashMap<(usize, usize), u64>,
+    beam: Point2<usize>,
+    grid: &Grid<GridValue>,
+    cache_timelines: &mut HashMap<Point2<usize>, u64>,
 ) -> u64 {
-    if let Some(timelines) = cache_timelines.get(&(x, y)) {
+    if let Some(timelines) = cache_timelines.get(&beam) {
         *timelines
     } else {
-        let timelines = if y + 1 < grid.len() {
-            match grid[y + 1][x] {
-                GridValue::Splitter => {
-                    let mut split_timelines = 0;
-                    if x > 0 {
-                        split_timelines += get_timelines((x - 1, y + 1), grid, cache_timelines);
-                    }
-                    if x + 1 < grid[0].len() {
-                        split_timelines += get_timelines((x + 1, y + 1), grid, cache_timelines);
-                    }
-                    split_timelines
-                }
-                _ => get_timelines((x, y + 1), grid, cache_timelines),
+        let timelines = match grid.offset(beam, Direction::Down) {
+            Some(below) if grid[below] == GridValue::Splitter => {
+                [Direction::Left, Direction::Right]
+                    .into_iter()
+                    .filter_map(|side| grid.offset(below, side))
+                    .map(|split| get_timelines(split, grid, cache_timelines))
+                    .sum()
             }
-        } else {
-            1
+            Some(below) => get_timelines(below, grid, cache_timelines),
+            None => 1,
         };
-        cache_timelines.insert((x, y), timelines);
+        cache_timelines.insert(beam, timelines);
         timelines
     }
 }
 
-fn parse_input(input: &str) -> Vec<Vec<GridValue>> {
-    input
-        .lines()
-        .map(|l| {
-            l.chars()
-                .map(|c| match c {
-                    'S' => GridValue::Beam,
-                    '^' => GridValue::Splitter,
-                    '.' => GridValue::Empty,
-                    _ => unreachable!("unknwon char"),
-                })
-                .collect()
-        })
-        .collect()
-}
-
-#[cfg(test)]
-mod tests {
-    use crate::puzzles::assert_eq_number;
-
-    use super::*;
-
-    static RAW_INPUT: &str = "\
-        .......S.......\n\
-        ...............\n\
-        .......^.......\n\
-        ...............\n\
-        ......^.^......\n\
-        ...............\n\
-        .....^.^.^.....\n\
-        ...............\n\
-        ....^.^...^....\n\
-        ...............\n\
-        ...^.^...^.^...\n\
-        ...............\n\
-        ..^...^.....^..\n\
-        ...............\n\
-        .^.^.^.^.^...^.\n\
-        ...............";
-
-    #[test]
-    fn solves_first() {
-        assert_eq_number(21, solve_first(String::from(RAW_INPUT)));
-    }
-
-    #[test]
-    fn solves_second() {
-        assert_eq_number(40, solve_second(String::from(RAW_INPUT)));
-    }
+fn parse_input(input: &str) -> Result<Grid<GridValue>, ParseError> {
+    Grid::parse(input, |c| match c {
+        'S' => Some(GridValue::Beam),
+        '^' => Some(GridValue::Splitter),
+        '.' => Some(GridValue::Empty),
+        _ => None,
+    })
 }
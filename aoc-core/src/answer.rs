@@ -0,0 +1,191 @@
+use std::fmt;
+
+pub use num_bigint::BigInt;
+
+/// Answer to a single part of a puzzle
+///
+/// Numbers compare equal regardless of the variant holding them, so a part
+/// returning `Unsigned(5)` matches an expected `Signed(5)`.
+#[derive(Clone, Debug)]
+pub enum Answer {
+    Signed(i64),
+    Unsigned(u128),
+    Big(BigInt),
+    Text(String),
+    Grid(PixelGrid),
+}
+
+impl Answer {
+    /// The numeric value, `None` for text and grids
+    pub fn as_number(&self) -> Option<BigInt> {
+        match self {
+            Answer::Signed(n) => Some(BigInt::from(*n)),
+            Answer::Unsigned(n) => Some(BigInt::from(*n)),
+            Answer::Big(n) => Some(n.clone()),
+            Answer::Text(_) | Answer::Grid(_) => None,
+        }
+    }
+}
+
+impl PartialEq for Answer {
+    fn eq(&self, other: &Answer) -> bool {
+        match (self, other) {
+            (Answer::Text(a), Answer::Text(b)) => a == b,
+            (Answer::Grid(a), Answer::Grid(b)) => a == b,
+            _ => match (self.as_number(), other.as_number()) {
+                (Some(a), Some(b)) => a == b,
+                _ => false,
+            },
+        }
+    }
+}
+
+impl Eq for Answer {}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Signed(n) => write!(f, "{n}"),
+            Answer::Unsigned(n) => write!(f, "{n}"),
+            Answer::Big(n) => write!(f, "{n}"),
+            Answer::Text(text) => write!(f, "{text}"),
+            Answer::Grid(grid) => write!(f, "{grid}"),
+        }
+    }
+}
+
+macro_rules! answer_from {
+    ($variant:ident, $target:ty, $($source:ty),*) => {
+        $(
+            impl From<$source> for Answer {
+                fn from(value: $source) -> Answer {
+                    Answer::$variant(value as $target)
+                }
+            }
+        )*
+    };
+}
+
+answer_from!(Signed, i64, i8, i16, i32, i64, isize);
+answer_from!(Unsigned, u128, u8, u16, u32, u64, usize, u128);
+
+impl From<i128> for Answer {
+    fn from(value: i128) -> Answer {
+        match i64::try_from(value) {
+            Ok(value) => Answer::Signed(value),
+            Err(_) => Answer::Big(BigInt::from(value)),
+        }
+    }
+}
+
+impl From<BigInt> for Answer {
+    fn from(value: BigInt) -> Answer {
+        Answer::Big(value)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(value: String) -> Answer {
+        Answer::Text(value)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Answer {
+        Answer::Text(String::from(value))
+    }
+}
+
+impl From<PixelGrid> for Answer {
+    fn from(value: PixelGrid) -> Answer {
+        Answer::Grid(value)
+    }
+}
+
+/// Image of lit and dark pixels, e.g. letters drawn on a screen
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PixelGrid {
+    width: usize,
+    pixels: Vec<bool>,
+}
+
+impl PixelGrid {
+    /// Creates a grid from rows of pixels, all rows need to have the same length
+    pub fn new(rows: Vec<Vec<bool>>) -> PixelGrid {
+        let width = rows.first().map_or(0, Vec::len);
+        assert!(
+            rows.iter().all(|row| row.len() == width),
+            "All rows of a pixel grid need to have the same length"
+        );
+        PixelGrid {
+            width,
+            pixels: rows.concat(),
+        }
+    }
+
+    /// Reads a grid drawn with `#` for lit pixels and any other character for dark ones
+    pub fn parse(drawing: &str) -> PixelGrid {
+        PixelGrid::new(
+            drawing
+                .trim()
+                .lines()
+                .map(|line| line.trim().chars().map(|c| c == '#').collect())
+                .collect(),
+        )
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.pixels.len().checked_div(self.width).unwrap_or(0)
+    }
+
+    pub fn is_lit(&self, x: usize, y: usize) -> bool {
+        x < self.width && self.pixels.get(y * self.width + x) == Some(&true)
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[bool]> {
+        self.pixels.chunks(self.width.max(1))
+    }
+}
+
+impl fmt::Display for PixelGrid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for &lit in row {
+                write!(f, "{}", if lit { '#' } else { '.' })?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compares_numbers_across_variants() {
+        assert_eq!(Answer::Signed(42), Answer::Unsigned(42));
+        assert_eq!(
+            Answer::from(u128::MAX),
+            Answer::Big(BigInt::from(u128::MAX))
+        );
+        assert_ne!(Answer::Signed(-1), Answer::from(u64::MAX));
+        assert_ne!(Answer::Signed(42), Answer::from("42"));
+        assert_eq!("-7", Answer::from(-7_i128).to_string());
+    }
+
+    #[test]
+    fn displays_grid() {
+        let grid = PixelGrid::parse("#..\n.##\n");
+        assert_eq!((3, 2), (grid.width(), grid.height()));
+        assert!(grid.is_lit(1, 1) && !grid.is_lit(1, 0) && !grid.is_lit(3, 1));
+        assert_eq!("#..\n.##", Answer::from(grid).to_string());
+    }
+}
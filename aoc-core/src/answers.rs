@@ -0,0 +1,240 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::answer::{Answer, BigInt, PixelGrid};
+use crate::runner::{DayReport, Failure, Outcome};
+use crate::solution::Part;
+
+/// Name of the answers file in the resources directory of a year
+pub const ANSWERS_FILE: &str = "answers.toml";
+
+/// Outcome of comparing an answer with the accepted one
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Pass,
+    /// The answer differs from the accepted one
+    Wrong,
+    /// The input could not be parsed
+    ParseFailed,
+    Panicked,
+    TimedOut,
+    /// The part is still to be written
+    NotImplemented,
+    /// No accepted answer is known yet, or there is no input
+    Missing,
+}
+
+impl Verdict {
+    /// Whether the part is broken, parts still to be written or checked are not
+    pub fn is_failure(self) -> bool {
+        !matches!(
+            self,
+            Verdict::Pass | Verdict::NotImplemented | Verdict::Missing
+        )
+    }
+}
+
+impl From<&Failure> for Verdict {
+    fn from(failure: &Failure) -> Verdict {
+        match failure {
+            Failure::Panicked(_) => Verdict::Panicked,
+            Failure::NotImplemented => Verdict::NotImplemented,
+            Failure::TimedOut(_) => Verdict::TimedOut,
+        }
+    }
+}
+
+/// Accepted answers for the real inputs of a year, keyed by day and part
+///
+/// Stored as TOML with a table per day:
+///
+/// ```toml
+/// [10]
+/// part1 = 12560
+/// part2 = "PLPAFBCL"
+/// ```
+#[derive(Debug, Default)]
+pub struct ExpectedAnswers {
+    answers: BTreeMap<(u8, Part), Answer>,
+}
+
+impl ExpectedAnswers {
+    /// Reads the answers file of a year, a missing file is an error as nothing
+    /// could be verified without it
+    pub fn load(path: &Path) -> Result<ExpectedAnswers, String> {
+        match fs::read_to_string(path) {
+            Ok(text) => ExpectedAnswers::parse(&text),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Err(format!(
+                "No accepted answers to verify against, {} does not exist",
+                path.display()
+            )),
+            Err(error) => Err(format!("Could not read {}: {error}", path.display())),
+        }
+    }
+
+    pub fn parse(text: &str) -> Result<ExpectedAnswers, String> {
+        let table: toml::Table = text
+            .parse()
+            .map_err(|error| format!("Invalid answers file: {error}"))?;
+        let mut answers = BTreeMap::new();
+        for (day_key, parts) in table {
+            let day = day_key
+                .parse()
+                .map_err(|_| format!("Expected a day instead of `{day_key}`"))?;
+            let parts = parts
+                .as_table()
+                .ok_or_else(|| format!("Expected a table of parts for day {day}"))?;
+            for (part_key, value) in parts {
+                let part = match part_key.as_str() {
+                    "part1" => Part::First,
+                    "part2" => Part::Second,
+                    _ => return Err(format!("Unknown part `{part_key}` for day {day}")),
+                };
+                let answer = parse_answer(value)
+                    .ok_or_else(|| format!("Unsupported answer for day {day} {part_key}"))?;
+                answers.insert((day, part), answer);
+            }
+        }
+        Ok(ExpectedAnswers { answers })
+    }
+
+    pub fn get(&self, day: u8, part: Part) -> Option<&Answer> {
+        self.answers.get(&(day, part))
+    }
+
+    pub fn check(&self, day: u8, part: Part, answer: &Answer) -> Verdict {
+        match self.get(day, part) {
+            Some(expected) if expected == answer => Verdict::Pass,
+            Some(_) => Verdict::Wrong,
+            None => Verdict::Missing,
+        }
+    }
+}
+
+/// Reads an answer written as a TOML value
+///
+/// Numbers too large for TOML integers can be written as strings, multi line
+/// strings of `#` and `.` are read as pixel grids.
+pub(crate) fn parse_answer(value: &toml::Value) -> Option<Answer> {
+    match value {
+        toml::Value::Integer(number) => Some(Answer::from(*number)),
+        toml::Value::String(text) if text.trim().contains('\n') => {
+            Some(Answer::from(PixelGrid::parse(text)))
+        }
+        toml::Value::String(text) => match text.parse::<BigInt>() {
+            Ok(number) => Some(Answer::from(number)),
+            Err(_) => Some(Answer::from(text.as_str())),
+        },
+        _ => None,
+    }
+}
+
+/// Result of comparing one part of a day with the accepted answer
+#[derive(Debug)]
+pub struct Check {
+    pub day: u8,
+    pub title: &'static str,
+    pub part: Part,
+    pub verdict: Verdict,
+    pub expected: Option<Answer>,
+    /// The answer, or why there is none
+    pub actual: String,
+}
+
+/// Compares both parts of every solved day with the accepted answers
+///
+/// Days whose input could not be parsed and parts that panicked or timed out
+/// fail, days without an input are missing.
+pub fn verify(reports: &[DayReport], expected: &ExpectedAnswers) -> Vec<Check> {
+    let mut checks = Vec::new();
+    for report in reports {
+        for part in Part::BOTH {
+            let (verdict, actual) = match &report.outcome {
+                Outcome::Solved(run) => match run.parts.iter().find(|p| p.part == part) {
+                    Some(part_run) => (
+                        expected.check(report.day, part, &part_run.answer),
+                        part_run.answer.to_string(),
+                    ),
+                    None => match report.failure(part) {
+                        Some(failure) => (Verdict::from(failure), failure.to_string()),
+                        None => continue,
+                    },
+                },
+                Outcome::ParseFailed(_) => (Verdict::ParseFailed, String::from("parse error")),
+                Outcome::ParseAborted(failure) => {
+                    (Verdict::from(failure), format!("parsing {failure}"))
+                }
+                Outcome::MissingInput => (Verdict::Missing, String::from("missing input")),
+            };
+            checks.push(Check {
+                day: report.day,
+                title: report.title,
+                part,
+                verdict,
+                expected: expected.get(report.day, part).cloned(),
+                actual,
+            });
+        }
+    }
+    checks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn requires_an_answers_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(ANSWERS_FILE);
+        let error = ExpectedAnswers::load(&path).unwrap_err();
+        assert!(error.contains("does not exist"), "{error}");
+
+        fs::write(&path, "[3]\npart1 = 157\n").unwrap();
+        let answers = ExpectedAnswers::load(&path).unwrap();
+        assert_eq!(Some(&Answer::from(157)), answers.get(3, Part::First));
+    }
+
+    #[test]
+    fn checks_answers() {
+        let answers = ExpectedAnswers::parse(
+            "
+            [1]
+            part1 = 24000
+            part2 = \"340282366920938463463374607431768211456\"
+
+            [10]
+            part2 = \"PLPAFBCL\"
+            ",
+        )
+        .unwrap();
+        let big = BigInt::from(u128::MAX) + 1;
+        assert_eq!(
+            Verdict::Pass,
+            answers.check(1, Part::First, &Answer::from(24000_u64))
+        );
+        assert_eq!(
+            Verdict::Wrong,
+            answers.check(1, Part::First, &Answer::from(24001))
+        );
+        assert_eq!(
+            Verdict::Pass,
+            answers.check(1, Part::Second, &Answer::from(big))
+        );
+        assert_eq!(
+            Verdict::Pass,
+            answers.check(10, Part::Second, &Answer::from("PLPAFBCL"))
+        );
+        assert_eq!(
+            Verdict::Missing,
+            answers.check(10, Part::First, &Answer::from(1))
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_parts() {
+        assert!(ExpectedAnswers::parse("[1]\npart3 = 1").is_err());
+    }
+}
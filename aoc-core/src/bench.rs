@@ -0,0 +1,78 @@
+use std::env;
+use std::time::Duration;
+
+use criterion::Criterion;
+
+use crate::config::Config;
+use crate::input_utils::InputDirs;
+use crate::solution::{Part, Puzzle, Puzzles, Run};
+
+/// Tag of solutions that take too long to be benchmarked by default
+pub const SLOW_TAG: &str = "slow";
+/// Environment variable that includes the solutions tagged [`SLOW_TAG`] in the benchmarks
+pub const SLOW_VAR: &str = "AOC_BENCH_SLOW";
+
+/// Benchmarks every day that has a real input as `day_XX/parse`, `day_XX/part1`
+/// and `day_XX/part2`, meant to be called from a bench target of a year
+///
+/// ```ignore
+/// fn puzzles(c: &mut Criterion) {
+///     aoc_core::bench::bench_puzzles(c, &get_all_puzzles());
+/// }
+///
+/// criterion_group!(benches, puzzles);
+/// criterion_main!(benches);
+/// ```
+///
+/// Only the step itself is measured, the parts are timed without parsing the
+/// input. The names stay the same between runs, so criterion compares every
+/// run with the previous one.
+pub fn bench_puzzles(c: &mut Criterion, puzzles: &Puzzles) {
+    let config = Config::load().unwrap_or_else(|error| panic!("{error}"));
+    let dirs = InputDirs::load(&config, puzzles.resources_dir());
+    let include_slow = env::var_os(SLOW_VAR).is_some_and(|value| !value.is_empty());
+
+    for puzzle in puzzles.iter() {
+        let day = puzzle.day;
+        if puzzle.metadata.tags.contains(&SLOW_TAG) && !include_slow {
+            eprintln!(
+                "Skipping day {day}, it is tagged {SLOW_TAG}, set {SLOW_VAR}=1 to include it"
+            );
+            continue;
+        }
+        let input = match dirs.read_input(day.into()) {
+            Ok(input) => input,
+            Err(_) => {
+                eprintln!("Skipping day {day}, it has no input");
+                continue;
+            }
+        };
+        if let Err(error) = puzzle.run(&input, &[]) {
+            eprintln!("Skipping day {day}, {}", error.render());
+            continue;
+        }
+
+        let mut group = c.benchmark_group(format!("day_{day:0>2}"));
+        group.bench_function("parse", |b| {
+            b.iter_custom(|iters| time(iters, || run(puzzle, &input, &[]).parse_duration))
+        });
+        for part in Part::BOTH {
+            group.bench_function(format!("part{part}"), |b| {
+                b.iter_custom(|iters| {
+                    time(iters, || run(puzzle, &input, &[part]).parts[0].duration)
+                })
+            });
+        }
+        group.finish();
+    }
+}
+
+fn run(puzzle: &Puzzle, input: &str, parts: &[Part]) -> Run {
+    puzzle
+        .run(input, parts)
+        .expect("the input was parsed before")
+}
+
+fn time(iters: u64, mut measure: impl FnMut() -> Duration) -> Duration {
+    (0..iters).map(|_| measure()).sum()
+}
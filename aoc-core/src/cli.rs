@@ -0,0 +1,729 @@
+use std::num::NonZeroUsize;
+use std::ops::RangeInclusive;
+use std::process;
+use std::thread;
+use std::time::Duration;
+
+use clap::{Parser, Subcommand};
+
+use crate::answer::Answer;
+use crate::answers::{self, ANSWERS_FILE, ExpectedAnswers};
+use crate::config::Config;
+use crate::examples::{EXAMPLES_FILE, Examples};
+use crate::fetch::{self, FetchError, Fetched, Fetcher, UreqHttp};
+use crate::input_utils::{InputDirs, InputSource};
+use crate::output::{self, Format, PartResult};
+use crate::params::{self, Params};
+use crate::perf::{self, HISTORY_FILE, History, Record, Trend};
+use crate::report;
+use crate::runner::{self, Days, Outcome};
+use crate::scaffold;
+use crate::solution::{Part, Puzzles};
+use crate::submit::{Attempt, LEDGER_FILE, Ledger, Response, Submitter};
+use crate::watch::{self, Answers, FileWatcher};
+
+#[derive(Parser)]
+#[command(args_conflicts_with_subcommands = true)]
+struct Args {
+    #[command(subcommand)]
+    command: Option<Command>,
+    /// List implemented days instead of solving a puzzle
+    #[arg(long, conflicts_with_all = ["day", "part"])]
+    list: bool,
+    /// The day of the puzzle
+    #[arg(required_unless_present = "list")]
+    day: Option<u8>,
+    /// The part of the puzzle, both parts are solved when omitted
+    #[arg(value_parser = clap::value_parser!(u8).range(1..=2))]
+    part: Option<u8>,
+    /// Override a param of the day, e.g. `--param row=10`, see `--list` for the params
+    #[arg(long = "param", value_name = "NAME=VALUE", value_parser = params::parse_assignment)]
+    params: Vec<(String, i64)>,
+    /// Read the input from this file instead, `-` reads the standard input
+    #[arg(long, value_name = "PATH", conflicts_with = "list")]
+    input: Option<InputSource>,
+    /// Give up on parsing or a part after this many seconds and report it as timed out
+    #[arg(long, value_name = "SECONDS", value_parser = parse_timeout, conflicts_with = "list")]
+    timeout: Option<Duration>,
+    /// Print the results as text, JSON or CSV
+    #[arg(long, value_enum, default_value_t = Format::Text, conflicts_with = "list")]
+    format: Format,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Solve both parts of several days and print a table of answers and timings
+    Run {
+        /// `all`, the year, a single day or a range of days like `3-7`
+        #[arg(default_value = "all")]
+        days: Days,
+        /// Number of days solved at once, 0 for one per core
+        #[arg(short, long, default_value_t = 1)]
+        jobs: usize,
+        /// Solve the days once more one after the other for accurate timings
+        #[arg(long)]
+        retime: bool,
+        /// Give up on parsing or a part after this many seconds and report it as timed out
+        #[arg(long, value_name = "SECONDS", value_parser = parse_timeout)]
+        timeout: Option<Duration>,
+        /// Print the results as text, JSON or CSV
+        #[arg(long, value_enum, default_value_t = Format::Text)]
+        format: Format,
+    },
+    /// Check the answers for the real inputs against the accepted ones in resources/answers.toml
+    Verify {
+        /// `all`, the year, a single day or a range of days like `3-7`
+        #[arg(default_value = "all")]
+        days: Days,
+        /// Number of days solved at once, 0 for one per core
+        #[arg(short, long, default_value_t = 1)]
+        jobs: usize,
+        /// Give up on parsing or a part after this many seconds and report it as timed out
+        #[arg(long, value_name = "SECONDS", value_parser = parse_timeout)]
+        timeout: Option<Duration>,
+        /// Print the results as text, JSON or CSV
+        #[arg(long, value_enum, default_value_t = Format::Text)]
+        format: Format,
+    },
+    /// Download inputs from adventofcode.com into the input directory, inputs already there are kept
+    Fetch {
+        /// A single day, a range of days like `3-7`, or `all` for every implemented day
+        days: Days,
+    },
+    /// Submit an answer to adventofcode.com, answers known to be wrong from earlier attempts are refused
+    Submit {
+        /// The day of the puzzle
+        day: u8,
+        /// The part of the puzzle
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: u8,
+        /// The answer to submit, the puzzle is solved for it when omitted
+        answer: Option<String>,
+    },
+    /// Create the module of a new day from a template and register it, with an example and an input to fill in
+    New {
+        /// The year of the crate
+        year: u16,
+        /// The day of the puzzle
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+        /// Title of the puzzle as shown on the website
+        #[arg(long)]
+        title: Option<String>,
+    },
+    /// Solve a day again whenever its module, examples or input change, after testing it on the examples
+    Watch {
+        /// The day of the puzzle
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+    },
+    /// Record timings to resources/perf_history.csv or report regressions
+    Perf {
+        #[command(subcommand)]
+        command: PerfCommand,
+    },
+}
+
+#[derive(Subcommand)]
+enum PerfCommand {
+    /// Solve every part several times and append the median time and peak memory to the history
+    Record {
+        /// `all`, the year, a single day or a range of days like `3-7`
+        #[arg(default_value = "all")]
+        days: Days,
+        /// How often every part is solved
+        #[arg(long, default_value_t = 5, value_parser = clap::value_parser!(u32).range(1..))]
+        runs: u32,
+        /// Give up on a part after this many seconds of all its runs and leave it out
+        #[arg(long, value_name = "SECONDS", value_parser = parse_timeout)]
+        timeout: Option<Duration>,
+        /// Print the recorded timings as text, JSON or CSV
+        #[arg(long, value_enum, default_value_t = Format::Text)]
+        format: Format,
+    },
+    /// Compare the latest timing of every part with a baseline and flag the regressions
+    Report {
+        /// Slowdown in percent that counts as a regression, smaller changes are noise
+        #[arg(long, default_value_t = 10.0)]
+        threshold: f64,
+        /// Commit to compare with, by default every part is compared with its previous timing
+        #[arg(long)]
+        baseline: Option<String>,
+        /// Print the comparisons as text, JSON or CSV
+        #[arg(long, value_enum, default_value_t = Format::Text)]
+        format: Format,
+    },
+}
+
+pub fn run(puzzles: &Puzzles) {
+    let args = Args::parse();
+
+    match args.command {
+        Some(Command::Run {
+            days,
+            jobs,
+            retime,
+            timeout,
+            format,
+        }) => run_days(puzzles, days, jobs, retime, timeout, format),
+        Some(Command::Verify {
+            days,
+            jobs,
+            timeout,
+            format,
+        }) => verify_days(puzzles, days, jobs, timeout, format),
+        Some(Command::Fetch { days }) => fetch_days(puzzles, days),
+        Some(Command::Submit { day, part, answer }) => {
+            let part = Part::from_number(part).expect("clap checks the part");
+            submit_answer(puzzles, day, part, answer);
+        }
+        Some(Command::New { year, day, title }) => new_day(puzzles, year, day, title),
+        Some(Command::Watch { day }) => watch_day(puzzles, day),
+        Some(Command::Perf {
+            command:
+                PerfCommand::Record {
+                    days,
+                    runs,
+                    timeout,
+                    format,
+                },
+        }) => record_perf(puzzles, days, runs, timeout, format),
+        Some(Command::Perf {
+            command:
+                PerfCommand::Report {
+                    threshold,
+                    baseline,
+                    format,
+                },
+        }) => report_perf(puzzles, threshold, baseline.as_deref(), format),
+        None if args.list => {
+            for puzzle in puzzles.iter() {
+                println!("Day {:>2}: {}", puzzle.day, puzzle.title);
+                for param in puzzle.params {
+                    println!("        {param} - {}", param.description);
+                }
+            }
+        }
+        None => {
+            let Some(day) = args.day else {
+                unreachable!("clap requires a day unless listing")
+            };
+            let mut overrides = Params::default();
+            for (name, value) in args.params {
+                overrides.set(name, value);
+            }
+            let source = args.input.unwrap_or_default();
+            solve_day(
+                puzzles,
+                day,
+                args.part.and_then(Part::from_number),
+                &source,
+                &overrides,
+                args.timeout,
+                args.format,
+            );
+        }
+    }
+}
+
+fn solve_day(
+    puzzles: &Puzzles,
+    day: u8,
+    part: Option<Part>,
+    source: &InputSource,
+    overrides: &Params,
+    timeout: Option<Duration>,
+    format: Format,
+) {
+    let parts = match part {
+        Some(part) => vec![part],
+        None => Part::BOTH.to_vec(),
+    };
+    if format == Format::Text {
+        match part {
+            Some(part) => println!("Solve Day {day} Puzzle Part {part}"),
+            None => println!("Solve Day {day} Puzzle Parts 1 and 2"),
+        }
+    }
+    let fail = |title: &'static str, error: String| -> ! {
+        match format {
+            Format::Text => eprintln!("{error}"),
+            _ => {
+                let results: Vec<PartResult> = parts
+                    .iter()
+                    .map(|&part| PartResult::failed(day, title, part, error.as_str()))
+                    .collect();
+                print_results(puzzles, format, &results);
+            }
+        }
+        process::exit(1);
+    };
+
+    let Some(puzzle) = puzzles.get(day) else {
+        let implemented: Vec<String> = puzzles.days().iter().map(u8::to_string).collect();
+        fail(
+            "",
+            format!(
+                "Day {day} is not implemented, implemented days are: {}",
+                implemented.join(", ")
+            ),
+        );
+    };
+
+    let params =
+        Params::resolve(puzzle.params, overrides).unwrap_or_else(|error| fail(puzzle.title, error));
+    let dirs = input_dirs(puzzles);
+    let input = source
+        .read(day.into(), &dirs)
+        .unwrap_or_else(|error| fail(puzzle.title, error.to_string()));
+
+    let report = runner::solve_input(puzzle, &input, &parts, &params, timeout);
+    let failed = !matches!(report.outcome, Outcome::Solved(_)) || !report.failures.is_empty();
+    if format != Format::Text {
+        let results: Vec<PartResult> = output::results(std::slice::from_ref(&report))
+            .into_iter()
+            .filter(|result| parts.contains(&result.part))
+            .collect();
+        print_results(puzzles, format, &results);
+        if failed {
+            process::exit(1);
+        }
+        return;
+    }
+
+    let run = match &report.outcome {
+        Outcome::Solved(run) => run,
+        Outcome::ParseFailed(error) => fail(puzzle.title, error.render()),
+        Outcome::ParseAborted(failure) => fail(puzzle.title, format!("Parsing {failure}")),
+        Outcome::MissingInput => unreachable!("the input was read"),
+    };
+    // Only read for the note on answers submitted before, solving works without it
+    let ledger = if run.parts.is_empty() {
+        None
+    } else {
+        Ledger::load(dirs.cache_dir().join(LEDGER_FILE))
+            .inspect_err(|error| eprintln!("Not showing the submitted answers, {error}"))
+            .ok()
+    };
+    println!("Parsed input in {:?}", run.parse_duration);
+    for &part in &parts {
+        let label = if parts.len() > 1 {
+            format!("Part {part} answer")
+        } else {
+            String::from("Answer")
+        };
+        let Some(part_run) = run.parts.iter().find(|p| p.part == part) else {
+            if let Some(failure) = report.failure(part) {
+                eprintln!("{label} failed: {failure}");
+            }
+            continue;
+        };
+        println!("{label}: {}", part_run.answer);
+        let answer = part_run.answer.to_string();
+        if let Some(known) = ledger.as_ref().and_then(|l| l.check(day, part, &answer)) {
+            println!("{known}");
+        }
+        println!("Solved in {:?}", part_run.duration);
+    }
+    if failed {
+        process::exit(1);
+    }
+}
+
+/// Prints results in one of the machine readable formats
+fn print_results(puzzles: &Puzzles, format: Format, results: &[PartResult]) {
+    match format {
+        Format::Json => println!("{}", output::json(puzzles.year(), results)),
+        Format::Csv => println!("{}", output::csv(puzzles.year(), results)),
+        Format::Text => unreachable!("commands print text themselves"),
+    }
+}
+
+fn input_dirs(puzzles: &Puzzles) -> InputDirs {
+    InputDirs::load(&load_config(), puzzles.resources_dir())
+}
+
+fn load_config() -> Config {
+    Config::load().unwrap_or_else(|error| {
+        eprintln!("{error}");
+        process::exit(1);
+    })
+}
+
+fn load_ledger(dirs: &InputDirs) -> Ledger {
+    Ledger::load(dirs.cache_dir().join(LEDGER_FILE)).unwrap_or_else(|error| {
+        eprintln!("{error}");
+        process::exit(1);
+    })
+}
+
+fn resolve_days(puzzles: &Puzzles, days: Days) -> RangeInclusive<u8> {
+    let Some(range) = days.resolve(puzzles.year()) else {
+        eprintln!("Only days of {} can be run here", puzzles.year());
+        process::exit(1);
+    };
+    range
+}
+
+fn run_days(
+    puzzles: &Puzzles,
+    days: Days,
+    jobs: usize,
+    retime: bool,
+    timeout: Option<Duration>,
+    format: Format,
+) {
+    let range = resolve_days(puzzles, days);
+
+    if format == Format::Text {
+        match days {
+            Days::Range(..) => println!("Solve {} Puzzles, days {days}", puzzles.year()),
+            _ => println!("Solve {} Puzzles", puzzles.year()),
+        }
+    }
+    let dirs = input_dirs(puzzles);
+    let mut reports = runner::run_days(puzzles, range, &dirs, job_count(jobs), timeout);
+    if retime {
+        runner::retime(puzzles, &mut reports, &dirs, timeout);
+    }
+    if format != Format::Text {
+        print_results(puzzles, format, &output::results(&reports));
+        return;
+    }
+    println!("{}", report::table(&reports));
+
+    for report in &reports {
+        if let Outcome::ParseFailed(error) = &report.outcome {
+            eprintln!("{}", error.render());
+        }
+    }
+}
+
+fn verify_days(
+    puzzles: &Puzzles,
+    days: Days,
+    jobs: usize,
+    timeout: Option<Duration>,
+    format: Format,
+) {
+    let range = resolve_days(puzzles, days);
+    let expected = ExpectedAnswers::load(&puzzles.resource(ANSWERS_FILE)).unwrap_or_else(|error| {
+        eprintln!("{error}");
+        process::exit(1);
+    });
+
+    if format == Format::Text {
+        println!("Verify {} Puzzles", puzzles.year());
+    }
+    let reports = runner::run_days(
+        puzzles,
+        range,
+        &input_dirs(puzzles),
+        job_count(jobs),
+        timeout,
+    );
+    let checks = answers::verify(&reports, &expected);
+    if format == Format::Text {
+        println!("{}", report::verify_table(&checks));
+    } else {
+        let mut results = output::results(&reports);
+        output::check_results(&mut results, &checks);
+        print_results(puzzles, format, &results);
+    }
+
+    if checks.iter().any(|check| check.verdict.is_failure()) {
+        process::exit(1);
+    }
+}
+
+fn parse_timeout(s: &str) -> Result<Duration, String> {
+    s.parse()
+        .ok()
+        .and_then(|seconds| Duration::try_from_secs_f64(seconds).ok())
+        .filter(|timeout| !timeout.is_zero())
+        .ok_or_else(|| format!("`{s}` is not a positive number of seconds"))
+}
+
+/// The number of days to solve at once, 0 meaning one per core
+fn job_count(jobs: usize) -> usize {
+    match jobs {
+        0 => thread::available_parallelism().map_or(1, NonZeroUsize::get),
+        jobs => jobs,
+    }
+}
+
+fn fetch_days(puzzles: &Puzzles, days: Days) {
+    let range = resolve_days(puzzles, days);
+    let days: Vec<u8> = match days {
+        Days::Range(..) => range.collect(),
+        _ => puzzles.days(),
+    };
+    let Some(session) = fetch::session(&load_config()) else {
+        eprintln!("{}", FetchError::NoSession);
+        process::exit(1);
+    };
+
+    let dirs = input_dirs(puzzles);
+    let mut fetcher = Fetcher::new(UreqHttp::default(), session);
+    for day in days {
+        match fetcher.fetch(puzzles.year(), day, &dirs) {
+            Ok(Fetched::Cached(path)) => println!("Day {day}: already in {}", path.display()),
+            Ok(Fetched::Downloaded(path)) => println!("Day {day}: saved to {}", path.display()),
+            Err(error) => {
+                eprintln!("Day {day}: {error}");
+                process::exit(1);
+            }
+        }
+    }
+}
+
+fn submit_answer(puzzles: &Puzzles, day: u8, part: Part, answer: Option<String>) {
+    let dirs = input_dirs(puzzles);
+    let answer = answer.unwrap_or_else(|| solve_for_submission(puzzles, day, part, &dirs));
+    let mut ledger = load_ledger(&dirs);
+    if let Some(known) = ledger.check(day, part, &answer) {
+        println!("Day {day} part {part}: {answer}");
+        println!("{known}");
+        if known.is_wrong() {
+            eprintln!("Not submitted, the answer is known to be wrong");
+            process::exit(1);
+        }
+        return;
+    }
+    let Some(session) = fetch::session(&load_config()) else {
+        eprintln!("{}", FetchError::NoSession);
+        process::exit(1);
+    };
+
+    println!("Submit {answer} for day {day} part {part}");
+    let reply = Submitter::new(UreqHttp::default(), session)
+        .submit(puzzles.year(), day, part, &answer)
+        .unwrap_or_else(|error| {
+            eprintln!("{error}");
+            process::exit(1);
+        });
+    println!("{}", reply.message);
+    if reply.response.is_verdict() {
+        let attempt = Attempt {
+            day,
+            part,
+            answer,
+            response: reply.response,
+        };
+        if let Err(error) = ledger.record(attempt) {
+            eprintln!("{error}");
+        }
+    }
+    if reply.response != Response::Correct {
+        process::exit(1);
+    }
+}
+
+fn solve_for_submission(puzzles: &Puzzles, day: u8, part: Part, dirs: &InputDirs) -> String {
+    let Some(puzzle) = puzzles.get(day) else {
+        eprintln!("Day {day} is not implemented, pass the answer to submit");
+        process::exit(1);
+    };
+    let input = dirs.read_input(day.into()).unwrap_or_else(|error| {
+        eprintln!("{error}");
+        process::exit(1);
+    });
+    match puzzle.solve(&input, part) {
+        Ok(Answer::Grid(grid)) => {
+            eprintln!("{grid}\nThe letters could not be recognized, pass the answer to submit");
+            process::exit(1);
+        }
+        Ok(answer) => answer.to_string(),
+        Err(error) => {
+            eprintln!("{}", error.render());
+            process::exit(1);
+        }
+    }
+}
+
+fn record_perf(
+    puzzles: &Puzzles,
+    days: Days,
+    runs: u32,
+    timeout: Option<Duration>,
+    format: Format,
+) {
+    let range = resolve_days(puzzles, days);
+    let dirs = input_dirs(puzzles);
+    let commit = perf::current_commit();
+    let timestamp = perf::timestamp();
+
+    let history = puzzles.resource(HISTORY_FILE);
+
+    let text = format == Format::Text;
+    if text {
+        println!(
+            "Record timings of commit {commit} to {}, median of {runs} runs",
+            history.display()
+        );
+    }
+    let mut recorded = Vec::new();
+    for puzzle in puzzles.iter().filter(|puzzle| range.contains(&puzzle.day)) {
+        let day = puzzle.day;
+        let Ok(input) = dirs.read_input(day.into()) else {
+            eprintln!("Day {day}: missing input");
+            continue;
+        };
+        let mut records = Vec::new();
+        for part in Part::BOTH {
+            let (puzzle, input) = (*puzzle, input.clone());
+            let measure = move || perf::measure(&puzzle, &input, part, runs as usize);
+            let measurement = match runner::guarded(measure, timeout) {
+                Ok(Ok(measurement)) => measurement,
+                Ok(Err(error)) => {
+                    eprintln!("{}", error.render());
+                    break;
+                }
+                Err(failure) => {
+                    eprintln!("Day {day} part {part}: {failure}");
+                    continue;
+                }
+            };
+            if text {
+                println!(
+                    "Day {day} part {part}: {:.1?}, peak memory {}",
+                    measurement.median,
+                    measurement
+                        .peak_memory
+                        .map_or(String::from("unknown"), report::format_bytes)
+                );
+            }
+            records.push(Record {
+                timestamp,
+                commit: commit.clone(),
+                day,
+                part,
+                median: measurement.median,
+                peak_memory: measurement.peak_memory,
+            });
+        }
+        if let Err(error) = History::append(&history, &records) {
+            eprintln!("{error}");
+            process::exit(1);
+        }
+        recorded.extend(records);
+    }
+    match format {
+        Format::Text => {}
+        Format::Json => println!("{}", output::records_json(puzzles.year(), &recorded)),
+        Format::Csv => println!("{}", output::records_csv(puzzles.year(), &recorded)),
+    }
+}
+
+fn report_perf(puzzles: &Puzzles, threshold: f64, baseline: Option<&str>, format: Format) {
+    let history = History::load(&puzzles.resource(HISTORY_FILE)).unwrap_or_else(|error| {
+        eprintln!("{error}");
+        process::exit(1);
+    });
+    let comparisons = perf::compare(&history, baseline, threshold);
+    let year = puzzles.year();
+    match format {
+        Format::Json => println!(
+            "{}",
+            output::comparisons_json(year, &comparisons, threshold)
+        ),
+        Format::Csv => println!("{}", output::comparisons_csv(year, &comparisons, threshold)),
+        Format::Text if comparisons.is_empty() => {
+            println!("Nothing recorded yet, record timings with `perf record`");
+            return;
+        }
+        Format::Text => {
+            match baseline {
+                Some(commit) => println!("Compare the latest timings with commit {commit}"),
+                None => println!("Compare the latest timings with the previous ones"),
+            }
+            println!("{}", report::perf_table(&comparisons));
+        }
+    }
+
+    if comparisons
+        .iter()
+        .any(|comparison| comparison.trend == Trend::Regressed)
+    {
+        process::exit(1);
+    }
+}
+
+fn new_day(puzzles: &Puzzles, year: u16, day: u8, title: Option<String>) {
+    if year != puzzles.year() {
+        eprintln!("Only days of {} can be created here", puzzles.year());
+        process::exit(1);
+    }
+    let title = title.unwrap_or_else(|| format!("Day {day}"));
+    // The crate may be elsewhere than the working directory
+    match scaffold::new_day(puzzles.crate_dir(), day, &title) {
+        Ok(done) => {
+            for step in done {
+                println!("{step}");
+            }
+            println!(
+                "The example test fails until the solution is written, run it with `cargo test day_{day:0>2}`"
+            );
+        }
+        Err(error) => {
+            eprintln!("{error}");
+            process::exit(1);
+        }
+    }
+}
+
+fn watch_day(puzzles: &Puzzles, day: u8) {
+    let root = puzzles.crate_dir();
+    let module = root
+        .join(scaffold::PUZZLES_DIR)
+        .join(format!("day_{day:0>2}.rs"));
+    if !module.is_file() {
+        eprintln!("{} not found", module.display());
+        process::exit(1);
+    }
+
+    let dirs = input_dirs(puzzles);
+    let mut previous = Answers::new();
+    loop {
+        let examples = Examples::load(&puzzles.resource(EXAMPLES_FILE)).unwrap_or_else(|error| {
+            eprintln!("{error}");
+            Examples::default()
+        });
+        let files = watch::watched_files(root, day, &examples, &dirs);
+        let watcher = FileWatcher::new(&files).unwrap_or_else(|error| {
+            eprintln!("{error}");
+            process::exit(1);
+        });
+
+        println!("Solve Day {day} Puzzle");
+        match watch::check_day(root, day) {
+            Ok(answers) => {
+                for line in watch::describe(&previous, &answers) {
+                    println!("{line}");
+                }
+                previous = answers;
+            }
+            Err(error) => eprintln!("{error}"),
+        }
+
+        let names: Vec<String> = files
+            .iter()
+            .map(|file| file.display().to_string())
+            .collect();
+        println!("Watching {}", names.join(", "));
+        match watcher.wait() {
+            Ok(changed) => {
+                let names: Vec<String> = changed
+                    .iter()
+                    .filter_map(|file| file.file_name())
+                    .map(|name| name.to_string_lossy().into_owned())
+                    .collect();
+                println!("\nChanged {}", names.join(", "));
+            }
+            Err(error) => {
+                eprintln!("{error}");
+                process::exit(1);
+            }
+        }
+    }
+}
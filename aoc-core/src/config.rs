@@ -0,0 +1,68 @@
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+/// Optional config file in the working directory
+pub const CONFIG_FILE: &str = "aoc.toml";
+
+/// Settings read from `aoc.toml`, all of them optional
+///
+/// ```toml
+/// input_dir = "../inputs"
+/// session = "53616c7465645f5f..."
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Config {
+    /// Directory to read the inputs from and to download them to
+    pub input_dir: Option<PathBuf>,
+    /// Session cookie of adventofcode.com used to download the inputs
+    pub session: Option<String>,
+}
+
+impl Config {
+    /// Reads the config file of the working directory, a missing file means nothing is configured
+    pub fn load() -> Result<Config, String> {
+        match fs::read_to_string(CONFIG_FILE) {
+            Ok(text) => Config::parse(&text),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(Config::default()),
+            Err(error) => Err(format!("Could not read {CONFIG_FILE}: {error}")),
+        }
+    }
+
+    pub fn parse(text: &str) -> Result<Config, String> {
+        let table: toml::Table = text
+            .parse()
+            .map_err(|error| format!("Invalid {CONFIG_FILE}: {error}"))?;
+        let mut config = Config::default();
+        for (key, value) in table {
+            let value = value
+                .as_str()
+                .ok_or_else(|| format!("Expected a string as {key} in {CONFIG_FILE}"))?;
+            match key.as_str() {
+                "input_dir" => config.input_dir = Some(PathBuf::from(value)),
+                "session" => config.session = Some(value.to_string()),
+                _ => return Err(format!("Unknown setting `{key}` in {CONFIG_FILE}")),
+            }
+        }
+        Ok(config)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_config() {
+        assert_eq!(
+            Config {
+                input_dir: Some(PathBuf::from("../inputs")),
+                session: Some(String::from("abc")),
+            },
+            Config::parse("input_dir = \"../inputs\"\nsession = \"abc\"").unwrap()
+        );
+        assert_eq!(Config::default(), Config::parse("").unwrap());
+        assert!(Config::parse("input_dir = 1").is_err());
+        assert!(Config::parse("input-dir = \"a\"").is_err());
+    }
+}
@@ -0,0 +1,259 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Groups of keys that are merged together, also known as union-find
+///
+/// Smaller groups are always attached to larger ones, so looking up the group
+/// of a key takes logarithmic time. Paths are not compressed, which keeps
+/// lookups from changing the set and lets changes be undone.
+#[derive(Clone, Debug)]
+pub struct DisjointSet<K> {
+    keys: Vec<K>,
+    indices: HashMap<K, usize>,
+    parent: Vec<usize>,
+    size: Vec<usize>,
+    components: usize,
+    history: Vec<Change>,
+}
+
+/// A change to a [`DisjointSet`] that can be undone
+#[derive(Clone, Copy, Debug)]
+enum Change {
+    Inserted,
+    Merged { child: usize, root: usize },
+}
+
+/// The state of a [`DisjointSet`] to roll back to
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Snapshot(usize);
+
+impl<K: Clone + Eq + Hash> DisjointSet<K> {
+    pub fn new() -> DisjointSet<K> {
+        DisjointSet {
+            keys: Vec::new(),
+            indices: HashMap::new(),
+            parent: Vec::new(),
+            size: Vec::new(),
+            components: 0,
+            history: Vec::new(),
+        }
+    }
+
+    /// Adds the key in a group of its own, `false` when it is already there
+    pub fn insert(&mut self, key: K) -> bool {
+        if self.indices.contains_key(&key) {
+            return false;
+        }
+        self.index_of(key);
+        true
+    }
+
+    /// Merges the groups of both keys, adding the keys that are not there yet,
+    /// `false` when they already were in the same group
+    pub fn union(&mut self, a: K, b: K) -> bool {
+        let a = self.index_of(a);
+        let b = self.index_of(b);
+        let (mut root, mut child) = (self.root(a), self.root(b));
+        if root == child {
+            return false;
+        }
+        if self.size[root] < self.size[child] {
+            (root, child) = (child, root);
+        }
+        self.parent[child] = root;
+        self.size[root] += self.size[child];
+        self.components -= 1;
+        self.history.push(Change::Merged { child, root });
+        true
+    }
+
+    /// The key representing the group of the key
+    pub fn find(&self, key: &K) -> Option<&K> {
+        let index = *self.indices.get(key)?;
+        Some(&self.keys[self.root(index)])
+    }
+
+    /// Whether both keys are in the same group
+    pub fn connected(&self, a: &K, b: &K) -> bool {
+        matches!((self.find(a), self.find(b)), (Some(a), Some(b)) if a == b)
+    }
+
+    /// Number of keys in the group of the key
+    pub fn size_of(&self, key: &K) -> Option<usize> {
+        let index = *self.indices.get(key)?;
+        Some(self.size[self.root(index)])
+    }
+
+    /// Number of keys
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// Number of groups
+    pub fn component_count(&self) -> usize {
+        self.components
+    }
+
+    /// Sizes of the groups, in the order the keys representing them were added
+    pub fn component_sizes(&self) -> Vec<usize> {
+        (0..self.keys.len())
+            .filter(|&index| self.parent[index] == index)
+            .map(|root| self.size[root])
+            .collect()
+    }
+
+    /// The keys of every group, the groups and the keys in the order they were added
+    pub fn components(&self) -> Vec<Vec<&K>> {
+        let mut groups: Vec<Vec<&K>> = Vec::new();
+        let mut group_of_root = HashMap::new();
+        for (index, key) in self.keys.iter().enumerate() {
+            let group = *group_of_root.entry(self.root(index)).or_insert_with(|| {
+                groups.push(Vec::new());
+                groups.len() - 1
+            });
+            groups[group].push(key);
+        }
+        groups
+    }
+
+    /// The current state, to return to with [`DisjointSet::rollback`]
+    pub fn snapshot(&self) -> Snapshot {
+        Snapshot(self.history.len())
+    }
+
+    /// Undoes all insertions and unions since the snapshot was taken
+    pub fn rollback(&mut self, snapshot: Snapshot) {
+        while self.history.len() > snapshot.0 {
+            self.undo();
+        }
+    }
+
+    /// Undoes the last insertion or union that changed the set, `false` when
+    /// there is nothing left to undo
+    pub fn undo(&mut self) -> bool {
+        match self.history.pop() {
+            Some(Change::Inserted) => {
+                let key = self.keys.pop().unwrap();
+                self.indices.remove(&key);
+                self.parent.pop();
+                self.size.pop();
+                self.components -= 1;
+                true
+            }
+            Some(Change::Merged { child, root }) => {
+                self.parent[child] = child;
+                self.size[root] -= self.size[child];
+                self.components += 1;
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn index_of(&mut self, key: K) -> usize {
+        if let Some(&index) = self.indices.get(&key) {
+            return index;
+        }
+        let index = self.keys.len();
+        self.keys.push(key.clone());
+        self.indices.insert(key, index);
+        self.parent.push(index);
+        self.size.push(1);
+        self.components += 1;
+        self.history.push(Change::Inserted);
+        index
+    }
+
+    fn root(&self, mut index: usize) -> usize {
+        while self.parent[index] != index {
+            index = self.parent[index];
+        }
+        index
+    }
+}
+
+impl<K: Clone + Eq + Hash> Default for DisjointSet<K> {
+    fn default() -> DisjointSet<K> {
+        DisjointSet::new()
+    }
+}
+
+/// Every key in a group of its own
+impl<K: Clone + Eq + Hash> FromIterator<K> for DisjointSet<K> {
+    fn from_iter<I: IntoIterator<Item = K>>(keys: I) -> DisjointSet<K> {
+        let mut set = DisjointSet::new();
+        for key in keys {
+            set.insert(key);
+        }
+        set
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merges_groups() {
+        let mut set: DisjointSet<u32> = (0..6).collect();
+        assert_eq!(6, set.component_count());
+        assert!(set.union(0, 1));
+        assert!(set.union(2, 1));
+        assert!(set.union(4, 5));
+        assert!(!set.union(0, 2));
+        assert!(set.connected(&0, &2));
+        assert!(!set.connected(&0, &3));
+        assert!(!set.connected(&0, &9));
+        assert_eq!(set.find(&0), set.find(&2));
+        assert_eq!(Some(3), set.size_of(&1));
+        assert_eq!(None, set.size_of(&9));
+        assert_eq!(3, set.component_count());
+        assert_eq!(vec![3, 1, 2], set.component_sizes());
+        assert_eq!(
+            vec![vec![&0, &1, &2], vec![&3], vec![&4, &5]],
+            set.components()
+        );
+    }
+
+    #[test]
+    fn adds_keys_when_merging() {
+        let mut set = DisjointSet::new();
+        assert!(set.union("start", "A"));
+        assert!(set.union("b", "end"));
+        assert!(set.insert("c"));
+        assert!(!set.insert("A"));
+        assert_eq!((5, 3), (set.len(), set.component_count()));
+        assert!(set.union("A", "end"));
+        assert!(set.connected(&"start", &"b"));
+        assert_eq!(Some(4), set.size_of(&"end"));
+    }
+
+    #[test]
+    fn rolls_back_changes() {
+        let mut set: DisjointSet<u32> = (0..4).collect();
+        set.union(0, 1);
+        let snapshot = set.snapshot();
+        set.union(2, 3);
+        set.union(1, 3);
+        set.union(7, 0);
+        assert_eq!(1, set.component_count());
+        assert_eq!(Some(5), set.size_of(&3));
+
+        assert!(set.undo());
+        assert_eq!((5, Some(4)), (set.len(), set.size_of(&0)));
+        assert!(set.undo());
+        assert_eq!((4, None), (set.len(), set.find(&7)));
+        set.rollback(snapshot);
+        assert_eq!(3, set.component_count());
+        assert!(set.connected(&0, &1) && !set.connected(&2, &3));
+        assert_eq!(vec![2, 1, 1], set.component_sizes());
+
+        set.rollback(Snapshot(0));
+        assert!(set.is_empty());
+        assert!(!set.undo());
+    }
+}
@@ -0,0 +1,273 @@
+use std::env;
+use std::fmt::Write;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::answer::Answer;
+use crate::answers::parse_answer;
+use crate::input_utils::{self, RESOURCES_DIR};
+use crate::params::Params;
+use crate::solution::{Part, Puzzles};
+
+/// Name of the examples file in the resources directory
+pub const EXAMPLES_FILE: &str = "examples.toml";
+
+/// An example input from the puzzle description together with its answers
+#[derive(Debug)]
+pub struct Example {
+    pub day: u8,
+    /// Position among the examples of the day, starting from 1
+    pub number: usize,
+    /// File in the resources directory holding the input
+    pub input: String,
+    /// Expected answers, parts without one are not checked
+    pub answers: Vec<(Part, Answer)>,
+    /// Overrides of the params of the day
+    pub params: Params,
+}
+
+impl Example {
+    /// Solves the example, describing every answer that differs from the expected one
+    pub fn check(&self, puzzles: &Puzzles) -> Result<(), String> {
+        let puzzle = puzzles
+            .get(self.day)
+            .ok_or_else(|| format!("Day {} is not implemented", self.day))?;
+        let path = puzzles.resource(&self.input);
+        let input = fs::read_to_string(&path)
+            .map_err(|error| format!("Could not read {}: {error}", path.display()))?;
+        let params = Params::resolve(puzzle.params, &self.params)?;
+        let parts: Vec<Part> = self.answers.iter().map(|&(part, _)| part).collect();
+        let run = puzzle
+            .run_with(&input, &parts, &params)
+            .map_err(|error| error.render())?;
+
+        let mistakes: Vec<String> = run
+            .parts
+            .iter()
+            .zip(&self.answers)
+            .filter(|(part_run, (_, expected))| part_run.answer != *expected)
+            .map(|(part_run, (part, expected))| {
+                format!("part {part}: expected {expected}, got {}", part_run.answer)
+            })
+            .collect();
+        if mistakes.is_empty() {
+            Ok(())
+        } else {
+            Err(mistakes.join("\n"))
+        }
+    }
+}
+
+/// Examples of a year, stored as TOML with an array of tables per day:
+///
+/// ```toml
+/// [[15]]
+/// part1 = 26
+/// part2 = 56000011
+/// params = { row = 10, bound = 20 }
+///
+/// [[15]]
+/// input = "day_15_input_test_2.txt"
+/// part1 = 0
+/// ```
+///
+/// The input is read from `day_XX_input_test.txt` unless another file is given.
+#[derive(Debug, Default)]
+pub struct Examples {
+    examples: Vec<Example>,
+}
+
+impl Examples {
+    /// Reads the examples file of the year, a missing file means there are no examples
+    pub fn load(path: &Path) -> Result<Examples, String> {
+        match fs::read_to_string(path) {
+            Ok(text) => Examples::parse(&text),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(Examples::default()),
+            Err(error) => Err(format!("Could not read {}: {error}", path.display())),
+        }
+    }
+
+    pub fn parse(text: &str) -> Result<Examples, String> {
+        let table: toml::Table = text
+            .parse()
+            .map_err(|error| format!("Invalid examples file: {error}"))?;
+        let mut examples = Vec::new();
+        for (day_key, entries) in table {
+            let day: u8 = day_key
+                .parse()
+                .map_err(|_| format!("Expected a day instead of `{day_key}`"))?;
+            let entries = entries
+                .as_array()
+                .ok_or_else(|| format!("Expected `[[{day}]]` tables for day {day}"))?;
+            for (index, entry) in entries.iter().enumerate() {
+                let entry = entry
+                    .as_table()
+                    .ok_or_else(|| format!("Expected `[[{day}]]` tables for day {day}"))?;
+                examples.push(parse_example(day, index + 1, entry)?);
+            }
+        }
+        examples.sort_by_key(|example| (example.day, example.number));
+        Ok(Examples { examples })
+    }
+
+    pub fn get(&self, day: u8, number: usize) -> Option<&Example> {
+        self.examples
+            .iter()
+            .find(|example| example.day == day && example.number == number)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Example> {
+        self.examples.iter()
+    }
+}
+
+fn parse_example(day: u8, number: usize, entry: &toml::Table) -> Result<Example, String> {
+    let mut example = Example {
+        day,
+        number,
+        input: input_utils::test_input_file_name(day as usize),
+        answers: Vec::new(),
+        params: Params::default(),
+    };
+    let context = format!("day {day} example {number}");
+    for (key, value) in entry {
+        match key.as_str() {
+            "input" => {
+                example.input = value
+                    .as_str()
+                    .ok_or_else(|| format!("Expected a file name as input of {context}"))?
+                    .to_string();
+            }
+            "part1" | "part2" => {
+                let part = if key == "part1" {
+                    Part::First
+                } else {
+                    Part::Second
+                };
+                let answer = parse_answer(value)
+                    .ok_or_else(|| format!("Unsupported answer for {context} {key}"))?;
+                example.answers.push((part, answer));
+            }
+            "params" => {
+                let params = value
+                    .as_table()
+                    .ok_or_else(|| format!("Expected a table of params for {context}"))?;
+                for (name, value) in params {
+                    let value = value
+                        .as_integer()
+                        .ok_or_else(|| format!("Expected a number as {name} of {context}"))?;
+                    example.params.set(name.as_str(), value);
+                }
+            }
+            _ => return Err(format!("Unknown key `{key}` for {context}")),
+        }
+    }
+    example.answers.sort_by_key(|&(part, _)| part);
+    Ok(example)
+}
+
+/// Checks an example and panics unless all of its answers are right, used by the generated tests
+pub fn assert_example(puzzles: &Puzzles, day: u8, number: usize) {
+    let examples =
+        Examples::load(&puzzles.resource(EXAMPLES_FILE)).unwrap_or_else(|error| panic!("{error}"));
+    let example = examples
+        .get(day, number)
+        .unwrap_or_else(|| panic!("Day {day} has no example {number}"));
+    if let Err(error) = example.check(puzzles) {
+        panic!("Day {day} example {number} failed:\n{error}");
+    }
+}
+
+/// Source of a test function for every example, calling `get_all_puzzles` of the parent module
+pub fn test_code(examples: &Examples) -> String {
+    let mut code =
+        format!("// Generated from {RESOURCES_DIR}/{EXAMPLES_FILE} by the build script\n");
+    for example in examples.iter() {
+        let (day, number) = (example.day, example.number);
+        write!(
+            code,
+            "\n#[test]\nfn day_{day:0>2}_example_{number}() {{\n    \
+             aoc_core::examples::assert_example(&super::get_all_puzzles(), {day}, {number});\n}}\n"
+        )
+        .unwrap();
+    }
+    code
+}
+
+/// Writes the tests of all examples to `examples.rs` in `OUT_DIR`, meant to be run from a build script
+///
+/// ```ignore
+/// #[cfg(test)]
+/// mod examples {
+///     include!(concat!(env!("OUT_DIR"), "/examples.rs"));
+/// }
+/// ```
+pub fn generate_tests() {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR")
+        .expect("CARGO_MANIFEST_DIR is set by cargo for build scripts");
+    let path = Path::new(&manifest_dir)
+        .join(RESOURCES_DIR)
+        .join(EXAMPLES_FILE);
+    println!("cargo:rerun-if-changed={}", path.display());
+    let examples = Examples::load(&path).unwrap_or_else(|error| panic!("{error}"));
+    let out_dir = env::var("OUT_DIR").expect("OUT_DIR is set by cargo for build scripts");
+    fs::write(
+        Path::new(&out_dir).join("examples.rs"),
+        test_code(&examples),
+    )
+    .expect("Could not write the example tests");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static EXAMPLES: &str = "
+        [[15]]
+        part1 = 26
+        params = { row = 10 }
+
+        [[6]]
+        part2 = 19
+
+        [[6]]
+        input = \"day_06_input_test_2.txt\"
+        part2 = 23
+        part1 = 5
+    ";
+
+    #[test]
+    fn parses_examples() {
+        let examples = Examples::parse(EXAMPLES).unwrap();
+        let days: Vec<(u8, usize)> = examples.iter().map(|e| (e.day, e.number)).collect();
+        assert_eq!(vec![(6, 1), (6, 2), (15, 1)], days);
+
+        let second = examples.get(6, 2).unwrap();
+        assert_eq!("day_06_input_test_2.txt", second.input);
+        assert_eq!(
+            vec![
+                (Part::First, Answer::from(5)),
+                (Part::Second, Answer::from(23))
+            ],
+            second.answers
+        );
+        let row = examples.get(15, 1).unwrap();
+        assert_eq!("day_15_input_test.txt", row.input);
+        assert_eq!(10, row.params.get::<i64>("row"));
+    }
+
+    #[test]
+    fn generates_a_test_per_example() {
+        let code = test_code(&Examples::parse(EXAMPLES).unwrap());
+        assert!(code.contains("fn day_06_example_2() {"));
+        assert!(code.contains("assert_example(&super::get_all_puzzles(), 15, 1);"));
+        assert_eq!(3, code.matches("#[test]").count());
+    }
+
+    #[test]
+    fn rejects_unknown_keys() {
+        assert!(Examples::parse("[[1]]\nanswer = 1").is_err());
+        assert!(Examples::parse("[1]\npart1 = 1").is_err());
+    }
+}
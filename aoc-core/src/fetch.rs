@@ -0,0 +1,352 @@
+use std::env;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::path::PathBuf;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::config::{CONFIG_FILE, Config};
+use crate::input_utils::{self, InputDirs};
+
+/// Environment variable holding the session cookie of adventofcode.com
+pub const SESSION_VAR: &str = "AOC_SESSION";
+pub const BASE_URL: &str = "https://adventofcode.com";
+/// Shortest time between two downloads, so the website is not flooded with requests
+pub const MIN_INTERVAL: Duration = Duration::from_secs(3);
+
+/// The session cookie from `AOC_SESSION` or else from the config
+pub fn session(config: &Config) -> Option<String> {
+    env::var(SESSION_VAR)
+        .ok()
+        .or_else(|| config.session.clone())
+        .map(|session| session.trim().trim_start_matches("session=").to_string())
+        .filter(|session| !session.is_empty())
+}
+
+/// The part of HTTP needed to download inputs and submit answers, so tests
+/// can talk to a local server
+pub trait Http {
+    /// Body of a successful GET request to `url`, sent with the session cookie
+    fn get(&self, url: &str, session: &str) -> Result<String, HttpError>;
+
+    /// Body of a successful POST of a form to `url`, sent with the session cookie
+    fn post(&self, url: &str, session: &str, form: &[(&str, &str)]) -> Result<String, HttpError>;
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum HttpError {
+    /// The server answered with an error status
+    Status(u16),
+    /// The server could not be reached or the answer not be read
+    Transport(String),
+}
+
+/// [`Http`] over the network
+pub struct UreqHttp {
+    agent: ureq::Agent,
+}
+
+impl Default for UreqHttp {
+    fn default() -> UreqHttp {
+        let agent = ureq::AgentBuilder::new()
+            .timeout(Duration::from_secs(30))
+            .user_agent(concat!("aoc-core/", env!("CARGO_PKG_VERSION")))
+            .build();
+        UreqHttp { agent }
+    }
+}
+
+impl Http for UreqHttp {
+    fn get(&self, url: &str, session: &str) -> Result<String, HttpError> {
+        let response = self
+            .agent
+            .get(url)
+            .set("Cookie", &format!("session={session}"))
+            .call();
+        read_body(response)
+    }
+
+    fn post(&self, url: &str, session: &str, form: &[(&str, &str)]) -> Result<String, HttpError> {
+        let response = self
+            .agent
+            .post(url)
+            .set("Cookie", &format!("session={session}"))
+            .send_form(form);
+        read_body(response)
+    }
+}
+
+fn read_body(response: Result<ureq::Response, ureq::Error>) -> Result<String, HttpError> {
+    response
+        .map_err(|error| match error {
+            ureq::Error::Status(status, _) => HttpError::Status(status),
+            ureq::Error::Transport(transport) => HttpError::Transport(transport.to_string()),
+        })?
+        .into_string()
+        .map_err(|error| HttpError::Transport(error.to_string()))
+}
+
+/// Where the input of a day ended up
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Fetched {
+    /// The input was already there and was not downloaded again
+    Cached(PathBuf),
+    Downloaded(PathBuf),
+}
+
+#[derive(Debug)]
+pub enum FetchError {
+    NoSession,
+    Http { url: String, error: HttpError },
+    Write { path: PathBuf, error: String },
+}
+
+impl fmt::Display for FetchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FetchError::NoSession => write!(
+                f,
+                "No session cookie, set {SESSION_VAR} or session in {CONFIG_FILE} to the session cookie of adventofcode.com"
+            ),
+            FetchError::Http {
+                url,
+                error: HttpError::Status(404),
+            } => write!(f, "{url} was not found, the puzzle may not be unlocked yet"),
+            FetchError::Http {
+                url,
+                error: HttpError::Status(status @ (400 | 500)),
+            } => write!(
+                f,
+                "{url} answered with status {status}, the session cookie may have expired"
+            ),
+            FetchError::Http {
+                url,
+                error: HttpError::Status(status),
+            } => write!(f, "{url} answered with status {status}"),
+            FetchError::Http {
+                url,
+                error: HttpError::Transport(error),
+            } => write!(f, "Could not reach {url}: {error}"),
+            FetchError::Write { path, error } => {
+                write!(f, "Could not write {}: {error}", path.display())
+            }
+        }
+    }
+}
+
+impl Error for FetchError {}
+
+/// Downloads puzzle inputs, at most one every [`MIN_INTERVAL`]
+pub struct Fetcher<H: Http> {
+    http: H,
+    session: String,
+    base_url: String,
+    interval: Duration,
+    last_request: Option<Instant>,
+}
+
+impl<H: Http> Fetcher<H> {
+    pub fn new(http: H, session: impl Into<String>) -> Fetcher<H> {
+        Fetcher {
+            http,
+            session: session.into(),
+            base_url: String::from(BASE_URL),
+            interval: MIN_INTERVAL,
+            last_request: None,
+        }
+    }
+
+    pub fn with_base_url(self, base_url: impl Into<String>) -> Fetcher<H> {
+        Fetcher {
+            base_url: base_url.into(),
+            ..self
+        }
+    }
+
+    pub fn with_interval(self, interval: Duration) -> Fetcher<H> {
+        Fetcher { interval, ..self }
+    }
+
+    /// Downloads the input of a day to the cache directory of `dirs`, unless
+    /// one of the directories already has it, empty placeholders are replaced
+    pub fn fetch(&mut self, year: u16, day: u8, dirs: &InputDirs) -> Result<Fetched, FetchError> {
+        if let Some(path) = dirs
+            .find_input(day.into())
+            .filter(|path| fs::metadata(path).is_ok_and(|metadata| metadata.len() > 0))
+        {
+            return Ok(Fetched::Cached(path));
+        }
+
+        if let Some(wait) = self
+            .last_request
+            .and_then(|last| self.interval.checked_sub(last.elapsed()))
+        {
+            thread::sleep(wait);
+        }
+        let url = format!("{}/{year}/day/{day}/input", self.base_url);
+        let response = self.http.get(&url, &self.session);
+        self.last_request = Some(Instant::now());
+        let input = response.map_err(|error| FetchError::Http { url, error })?;
+
+        let dir = dirs.cache_dir();
+        let path = dir.join(input_utils::input_file_name(day.into()));
+        fs::create_dir_all(dir)
+            .and_then(|()| fs::write(&path, input))
+            .map_err(|error| FetchError::Write {
+                path: path.clone(),
+                error: error.to_string(),
+            })?;
+        Ok(Fetched::Downloaded(path))
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use std::cell::RefCell;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::sync::mpsc::{self, Receiver};
+
+    use super::*;
+
+    /// Answers a single request on a local port, sending back the whole request
+    pub(crate) fn serve_once(
+        status: &'static str,
+        body: &'static str,
+    ) -> (String, Receiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = format!("http://{}", listener.local_addr().unwrap());
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut request = String::new();
+            while reader.read_line(&mut request).unwrap() > 2 {}
+            let length = request
+                .lines()
+                .find_map(|line| {
+                    line.to_lowercase()
+                        .strip_prefix("content-length: ")?
+                        .parse()
+                        .ok()
+                })
+                .unwrap_or(0);
+            let mut content = vec![0; length];
+            reader.read_exact(&mut content).unwrap();
+            request.push_str(&String::from_utf8(content).unwrap());
+            write!(
+                reader.get_mut(),
+                "HTTP/1.1 {status}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            )
+            .unwrap();
+            sender.send(request).unwrap();
+        });
+        (address, receiver)
+    }
+
+    #[test]
+    fn downloads_once_and_caches() {
+        let dir = tempfile::tempdir().unwrap();
+        let dirs = InputDirs::new(vec![dir.path().to_path_buf()]);
+        let (address, requests) = serve_once("200 OK", "1000\n2000\n");
+        let mut fetcher = Fetcher::new(UreqHttp::default(), "abc").with_base_url(address);
+
+        let path = dir.path().join("day_01_input.txt");
+        assert_eq!(
+            Fetched::Downloaded(path.clone()),
+            fetcher.fetch(2022, 1, &dirs).unwrap()
+        );
+        assert_eq!("1000\n2000\n", fs::read_to_string(&path).unwrap());
+        let request = requests.recv().unwrap().to_lowercase();
+        assert!(request.starts_with("get /2022/day/1/input http/1.1\r\n"));
+        assert!(request.contains("\r\ncookie: session=abc\r\n"));
+
+        assert_eq!(
+            Fetched::Cached(path),
+            fetcher.fetch(2022, 1, &dirs).unwrap()
+        );
+    }
+
+    #[test]
+    fn reports_locked_puzzles() {
+        let dir = tempfile::tempdir().unwrap();
+        let dirs = InputDirs::new(vec![dir.path().to_path_buf()]);
+        let (address, _) = serve_once("404 Not Found", "Not found");
+        let mut fetcher = Fetcher::new(UreqHttp::default(), "abc").with_base_url(&address);
+
+        let error = fetcher.fetch(2025, 25, &dirs).unwrap_err();
+        assert_eq!(
+            format!(
+                "{address}/2025/day/25/input was not found, the puzzle may not be unlocked yet"
+            ),
+            error.to_string()
+        );
+        assert!(dirs.find_input(25).is_none());
+    }
+
+    struct Recording {
+        requests: RefCell<Vec<(String, Instant)>>,
+    }
+
+    impl Http for &Recording {
+        fn get(&self, url: &str, _session: &str) -> Result<String, HttpError> {
+            self.requests
+                .borrow_mut()
+                .push((url.to_string(), Instant::now()));
+            Ok(String::from("1\n"))
+        }
+
+        fn post(
+            &self,
+            url: &str,
+            _session: &str,
+            _form: &[(&str, &str)],
+        ) -> Result<String, HttpError> {
+            unreachable!("inputs are only downloaded, not posted to {url}")
+        }
+    }
+
+    #[test]
+    fn replaces_empty_placeholders() {
+        let dir = tempfile::tempdir().unwrap();
+        let dirs = InputDirs::new(vec![dir.path().to_path_buf()]);
+        let path = dir.path().join("day_09_input.txt");
+        fs::write(&path, "").unwrap();
+        let http = Recording {
+            requests: RefCell::new(Vec::new()),
+        };
+        let mut fetcher = Fetcher::new(&http, "abc");
+
+        assert_eq!(
+            Fetched::Downloaded(path.clone()),
+            fetcher.fetch(2025, 9, &dirs).unwrap()
+        );
+        assert_eq!("1\n", fs::read_to_string(&path).unwrap());
+    }
+
+    #[test]
+    fn waits_between_downloads() {
+        let dir = tempfile::tempdir().unwrap();
+        let dirs = InputDirs::new(vec![dir.path().to_path_buf()]);
+        let http = Recording {
+            requests: RefCell::new(Vec::new()),
+        };
+        let mut fetcher = Fetcher::new(&http, "abc").with_interval(Duration::from_millis(50));
+        for day in [3, 4, 3] {
+            fetcher.fetch(2022, day, &dirs).unwrap();
+        }
+
+        let requests = http.requests.borrow();
+        let urls: Vec<&str> = requests.iter().map(|(url, _)| url.as_str()).collect();
+        assert_eq!(
+            vec![
+                "https://adventofcode.com/2022/day/3/input",
+                "https://adventofcode.com/2022/day/4/input"
+            ],
+            urls
+        );
+        assert!(requests[1].1 - requests[0].1 >= Duration::from_millis(50));
+    }
+}
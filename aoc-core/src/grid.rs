@@ -0,0 +1,437 @@
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+use crate::parse::ParseError;
+use crate::point::{Direction, Point2};
+
+/// A rectangular map of cells stored row by row
+///
+/// Cells are addressed by [`Point2`] positions, `x` counting columns from the
+/// left and `y` counting rows from the top. Moves take a [`Direction`] or a
+/// vector.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// A grid with every cell set to `value`
+    pub fn new(width: usize, height: usize, value: T) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    /// A grid holding the rows, which have to be equally long
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Result<Grid<T>, String> {
+        let width = rows.first().map_or(0, Vec::len);
+        if let Some(y) = rows.iter().position(|row| row.len() != width) {
+            return Err(format!(
+                "Row {y} has {} cells instead of {width}",
+                rows[y].len()
+            ));
+        }
+        let height = rows.len();
+        Ok(Grid {
+            width,
+            height,
+            cells: rows.into_iter().flatten().collect(),
+        })
+    }
+
+    /// Reads a map with a character per cell and a line per row, `cell`
+    /// returns `None` for characters that are not allowed
+    ///
+    /// Blank lines are only allowed after the map.
+    pub fn parse(
+        input: &str,
+        mut cell: impl FnMut(char) -> Option<T>,
+    ) -> Result<Grid<T>, ParseError> {
+        let mut cells = Vec::new();
+        let mut width = None;
+        let mut height = 0;
+        let mut blank = None;
+        for line in input.lines() {
+            if line.is_empty() {
+                blank = blank.or(Some(line));
+                continue;
+            }
+            if let Some(blank) = blank {
+                return Err(ParseError::at(input, blank, "Unexpected blank line"));
+            }
+            let before = cells.len();
+            for (i, c) in line.char_indices() {
+                let value = cell(c).ok_or_else(|| {
+                    ParseError::at(input, &line[i..i + c.len_utf8()], "Unexpected character")
+                })?;
+                cells.push(value);
+            }
+            let row_width = cells.len() - before;
+            match width {
+                Some(width) if width != row_width => {
+                    return Err(ParseError::at(
+                        input,
+                        line,
+                        format!("Expected a row of {width} cells"),
+                    ));
+                }
+                _ => width = Some(row_width),
+            }
+            height += 1;
+        }
+        Ok(Grid {
+            width: width.unwrap_or(0),
+            height,
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, position: Point2<usize>) -> bool {
+        position.x < self.width && position.y < self.height
+    }
+
+    pub fn get(&self, position: Point2<usize>) -> Option<&T> {
+        self.contains(position)
+            .then(|| &self.cells[self.index(position)])
+    }
+
+    pub fn get_mut(&mut self, position: Point2<usize>) -> Option<&mut T> {
+        if self.contains(position) {
+            let index = self.index(position);
+            Some(&mut self.cells[index])
+        } else {
+            None
+        }
+    }
+
+    /// The position moved by `offset`, a [`Direction`] or a vector, `None`
+    /// when it leaves the grid
+    pub fn offset(
+        &self,
+        position: Point2<usize>,
+        offset: impl Into<Point2<isize>>,
+    ) -> Option<Point2<usize>> {
+        let offset = offset.into();
+        let position = Point2::new(
+            position.x.checked_add_signed(offset.x)?,
+            position.y.checked_add_signed(offset.y)?,
+        );
+        self.contains(position).then_some(position)
+    }
+
+    /// The position moved by `offset`, leaving the grid on one side enters it
+    /// on the opposite side, `None` when the grid is empty
+    pub fn wrapping_offset(
+        &self,
+        position: Point2<usize>,
+        offset: impl Into<Point2<isize>>,
+    ) -> Option<Point2<usize>> {
+        if self.width == 0 || self.height == 0 {
+            return None;
+        }
+        let offset = offset.into();
+        let wrap = |value: usize, delta: isize, size: usize| {
+            (value as isize + delta).rem_euclid(size as isize) as usize
+        };
+        Some(Point2::new(
+            wrap(position.x, offset.x, self.width),
+            wrap(position.y, offset.y, self.height),
+        ))
+    }
+
+    /// Positions of the cells sharing an edge with the cell, clockwise starting up
+    pub fn neighbors4(&self, position: Point2<usize>) -> impl Iterator<Item = Point2<usize>> + '_ {
+        Direction::ORTHOGONAL
+            .into_iter()
+            .filter_map(move |direction| self.offset(position, direction))
+    }
+
+    /// Positions of the cells sharing an edge or a corner with the cell, clockwise starting up
+    pub fn neighbors8(&self, position: Point2<usize>) -> impl Iterator<Item = Point2<usize>> + '_ {
+        Direction::ALL
+            .into_iter()
+            .filter_map(move |direction| self.offset(position, direction))
+    }
+
+    /// Positions from the cell in steps of `step`, a [`Direction`] or a
+    /// vector, up to the edge, the cell itself not included
+    pub fn ray(
+        &self,
+        position: Point2<usize>,
+        step: impl Into<Point2<isize>>,
+    ) -> impl Iterator<Item = Point2<usize>> + '_ {
+        let step = step.into();
+        let mut current = position;
+        std::iter::from_fn(move || {
+            current = self.offset(current, step)?;
+            Some(current)
+        })
+    }
+
+    /// All positions, row by row
+    pub fn positions(&self) -> impl Iterator<Item = Point2<usize>> + use<T> {
+        let width = self.width;
+        (0..self.cells.len()).map(move |i| Point2::new(i % width, i / width))
+    }
+
+    /// All cells with their positions, row by row
+    pub fn iter(&self) -> impl Iterator<Item = (Point2<usize>, &T)> {
+        self.positions().zip(&self.cells)
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(|y| self.row(y))
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        (0..self.height).map(move |y| &self[Point2::new(x, y)])
+    }
+
+    /// Position of the first cell with the value, row by row
+    pub fn find(&self, value: &T) -> Option<Point2<usize>>
+    where
+        T: PartialEq,
+    {
+        self.position(|cell| cell == value)
+    }
+
+    /// Position of the first cell matching `predicate`, row by row
+    pub fn position(&self, predicate: impl Fn(&T) -> bool) -> Option<Point2<usize>> {
+        self.iter()
+            .find(|(_, cell)| predicate(cell))
+            .map(|(position, _)| position)
+    }
+
+    /// A grid of the same size with `f` applied to every cell
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    /// The grid mirrored along its diagonal, rows become columns
+    pub fn transpose(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        self.rebuild(self.height, self.width, |p| Point2::new(p.y, p.x))
+    }
+
+    /// The grid turned a quarter clockwise
+    pub fn rotate_clockwise(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        self.rebuild(self.height, self.width, |p| {
+            Point2::new(p.y, self.height - 1 - p.x)
+        })
+    }
+
+    /// The grid turned a quarter counterclockwise
+    pub fn rotate_counterclockwise(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        self.rebuild(self.height, self.width, |p| {
+            Point2::new(self.width - 1 - p.y, p.x)
+        })
+    }
+
+    /// A line per row with a character per cell
+    pub fn render(&self, cell: impl Fn(&T) -> char) -> String {
+        self.rows()
+            .map(|row| row.iter().map(&cell).collect::<String>())
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    fn index(&self, position: Point2<usize>) -> usize {
+        position.y * self.width + position.x
+    }
+
+    /// A grid of the given size whose cells are taken from the positions `source` gives
+    fn rebuild(
+        &self,
+        width: usize,
+        height: usize,
+        source: impl Fn(Point2<usize>) -> Point2<usize>,
+    ) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let cells = (0..width * height)
+            .map(|i| self[source(Point2::new(i % width, i / width))].clone())
+            .collect();
+        Grid {
+            width,
+            height,
+            cells,
+        }
+    }
+}
+
+impl<T> Index<Point2<usize>> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: Point2<usize>) -> &T {
+        assert!(
+            self.contains(position),
+            "{position} is outside of the {}x{} grid",
+            self.width,
+            self.height
+        );
+        &self.cells[Grid::index(self, position)]
+    }
+}
+
+impl<T> IndexMut<Point2<usize>> for Grid<T> {
+    fn index_mut(&mut self, position: Point2<usize>) -> &mut T {
+        assert!(
+            self.contains(position),
+            "{position} is outside of the {}x{} grid",
+            self.width,
+            self.height
+        );
+        let index = Grid::index(self, position);
+        &mut self.cells[index]
+    }
+}
+
+/// A line per row with the cells written next to each other
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid() -> Grid<char> {
+        Grid::parse("abc\ndef\n", Some).unwrap()
+    }
+
+    #[test]
+    fn parses_maps() {
+        let grid = grid();
+        assert_eq!((3, 2), (grid.width(), grid.height()));
+        assert_eq!('f', grid[Point2::new(2, 1)]);
+        assert_eq!(None, grid.get(Point2::new(3, 0)));
+        assert_eq!("abc\ndef", grid.to_string());
+
+        let error = Grid::parse("12\n3x\n", |c| c.to_digit(10)).unwrap_err();
+        assert_eq!("Unexpected character", error.message);
+        assert!(Grid::parse("12\n345\n", |c| c.to_digit(10)).is_err());
+        let error = Grid::parse("12\n\n34\n", |c| c.to_digit(10)).unwrap_err();
+        assert_eq!(
+            (2, "Unexpected blank line"),
+            (error.line, error.message.as_str())
+        );
+        assert_eq!(
+            2,
+            Grid::parse("12\n34\n\n", |c| c.to_digit(10))
+                .unwrap()
+                .height()
+        );
+        assert!(Grid::from_rows(vec![vec![1, 2], vec![3]]).is_err());
+        assert_eq!(
+            Grid::from_rows(vec![vec!['a', 'b', 'c'], vec!['d', 'e', 'f']]),
+            Ok(grid)
+        );
+    }
+
+    #[test]
+    fn finds_neighbors() {
+        let grid = Grid::new(3, 3, 0);
+        let center = Point2::new(1, 1);
+        assert_eq!(
+            vec![Point2::new(1, 0), Point2::new(0, 1)],
+            grid.neighbors4(Point2::new(0, 0)).collect::<Vec<_>>()
+        );
+        assert_eq!(8, grid.neighbors8(center).count());
+        assert_eq!(
+            vec![Point2::new(2, 1), Point2::new(2, 2), Point2::new(1, 2)],
+            grid.neighbors8(center)
+                .filter(|p| p.x + p.y >= 3)
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(None, grid.offset(Point2::new(2, 0), Direction::Right));
+        assert_eq!(
+            Some(Point2::new(0, 1)),
+            grid.offset(center, Direction::Left)
+        );
+        assert_eq!(
+            Some(Point2::new(2, 0)),
+            grid.offset(Point2::new(0, 1), Point2::new(2, -1))
+        );
+        assert_eq!(
+            Some(Point2::new(0, 2)),
+            grid.wrapping_offset(Point2::new(2, 0), Direction::UpRight)
+        );
+        let empty = Grid::new(0, 3, 0);
+        assert_eq!(
+            None,
+            empty.wrapping_offset(Point2::new(0, 0), Direction::Down)
+        );
+        assert_eq!(0, empty.neighbors8(Point2::new(0, 0)).count());
+    }
+
+    #[test]
+    fn walks_rows_columns_and_rays() {
+        let grid = grid();
+        assert_eq!(['d', 'e', 'f'], grid.row(1));
+        assert_eq!(vec![&'b', &'e'], grid.column(1).collect::<Vec<_>>());
+        assert_eq!(
+            vec![Point2::new(1, 1), Point2::new(0, 1)],
+            grid.ray(Point2::new(2, 1), Direction::Left)
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(0, grid.ray(Point2::new(0, 0), Direction::Up).count());
+        assert_eq!(Some(Point2::new(1, 1)), grid.find(&'e'));
+        assert_eq!(Some(Point2::new(0, 1)), grid.position(|c| *c > 'c'));
+    }
+
+    #[test]
+    fn transforms_grids() {
+        let grid = grid();
+        assert_eq!("ad\nbe\ncf", grid.transpose().to_string());
+        assert_eq!("da\neb\nfc", grid.rotate_clockwise().to_string());
+        assert_eq!("cf\nbe\nad", grid.rotate_counterclockwise().to_string());
+        assert_eq!(grid, grid.rotate_clockwise().rotate_counterclockwise());
+        assert_eq!(
+            "#..\n...",
+            grid.map(|&c| c == 'a')
+                .render(|&lit| if lit { '#' } else { '.' })
+        );
+    }
+}
@@ -0,0 +1,184 @@
+use std::env;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use crate::config::{CONFIG_FILE, Config};
+
+/// Environment variable naming the directory to read the inputs from
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+pub(crate) const RESOURCES_DIR: &str = "resources";
+
+/// Name of the file holding the real input of the day
+pub fn input_file_name(day: usize) -> String {
+    format!("day_{day:0>2}_input.txt")
+}
+
+/// Name of the file holding the first example of the day
+pub fn test_input_file_name(day: usize) -> String {
+    format!("day_{day:0>2}_input_test.txt")
+}
+
+/// Directories the inputs are looked up in, most preferred first
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InputDirs {
+    dirs: Vec<PathBuf>,
+}
+
+impl InputDirs {
+    /// Panics when `dirs` is empty, there would be nowhere to store the inputs
+    pub fn new(dirs: Vec<PathBuf>) -> InputDirs {
+        assert!(!dirs.is_empty(), "Expected at least one input directory");
+        InputDirs { dirs }
+    }
+
+    /// The directory named by `AOC_INPUT_DIR`, the `input_dir` of the config,
+    /// `resources` in the working directory when there is one and finally
+    /// `fallback`, e.g. the resources of the crate
+    ///
+    /// Without any of them the inputs are stored in `resources` of the working directory.
+    pub fn load(config: &Config, fallback: Option<&Path>) -> InputDirs {
+        let mut dirs = Vec::new();
+        if let Some(dir) = env::var_os(INPUT_DIR_VAR).filter(|dir| !dir.is_empty()) {
+            dirs.push(PathBuf::from(dir));
+        }
+        dirs.extend(config.input_dir.clone());
+        let resources = PathBuf::from(RESOURCES_DIR);
+        if resources.is_dir() || fallback.is_none() {
+            dirs.push(resources);
+        }
+        dirs.extend(fallback.map(Path::to_path_buf));
+        dirs.dedup();
+        InputDirs { dirs }
+    }
+
+    /// Directory new inputs are stored in, the most preferred one even when it
+    /// does not exist yet, it is created when storing the first input
+    pub fn cache_dir(&self) -> &Path {
+        &self.dirs[0]
+    }
+
+    /// Path of the input of the day in the first directory that has it
+    pub fn find_input(&self, day: usize) -> Option<PathBuf> {
+        let file_name = input_file_name(day);
+        self.dirs
+            .iter()
+            .map(|dir| dir.join(&file_name))
+            .find(|path| path.is_file())
+    }
+
+    pub fn read_input(&self, day: usize) -> Result<String, InputError> {
+        let file_name = input_file_name(day);
+        let mut tried = Vec::new();
+        for dir in &self.dirs {
+            let path = dir.join(&file_name);
+            match fs::read_to_string(&path) {
+                Ok(input) => return Ok(input),
+                Err(error) => tried.push((path, error.to_string())),
+            }
+        }
+        Err(InputError { day, tried })
+    }
+}
+
+/// Where to read the input of a day from
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum InputSource {
+    /// The input file of the day in the [`InputDirs`]
+    #[default]
+    Dirs,
+    File(PathBuf),
+    Stdin,
+}
+
+impl InputSource {
+    pub fn read(&self, day: usize, dirs: &InputDirs) -> Result<String, InputError> {
+        let (path, result) = match self {
+            InputSource::Dirs => return dirs.read_input(day),
+            InputSource::File(path) => (path.clone(), fs::read_to_string(path)),
+            InputSource::Stdin => (PathBuf::from("-"), io::read_to_string(io::stdin())),
+        };
+        result.map_err(|error| InputError {
+            day,
+            tried: vec![(path, error.to_string())],
+        })
+    }
+}
+
+impl FromStr for InputSource {
+    type Err = String;
+
+    /// `-` stands for the standard input, anything else is a path
+    fn from_str(s: &str) -> Result<InputSource, String> {
+        match s {
+            "" => Err(String::from(
+                "Expected a path or `-` for the standard input",
+            )),
+            "-" => Ok(InputSource::Stdin),
+            path => Ok(InputSource::File(PathBuf::from(path))),
+        }
+    }
+}
+
+/// Error raised when no input could be read for a day
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InputError {
+    pub day: usize,
+    /// Every path that was tried with the reason it could not be read
+    pub tried: Vec<(PathBuf, String)>,
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Could not read the input of day {}, tried:", self.day)?;
+        for (path, reason) in &self.tried {
+            write!(f, "\n    {}: {reason}", path.display())?;
+        }
+        write!(
+            f,
+            "\nPass a file with --input, `-` for the standard input, or set the directory with {INPUT_DIR_VAR} or input_dir in {CONFIG_FILE}"
+        )
+    }
+}
+
+impl Error for InputError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lists_tried_paths() {
+        let dirs = InputDirs::new(vec![PathBuf::from("missing"), PathBuf::from("resources")]);
+        let error = dirs.read_input(3).unwrap_err();
+        let paths: Vec<&Path> = error.tried.iter().map(|(path, _)| path.as_path()).collect();
+        assert_eq!(
+            vec![
+                Path::new("missing/day_03_input.txt"),
+                Path::new("resources/day_03_input.txt")
+            ],
+            paths
+        );
+        assert!(error.to_string().starts_with(
+            "Could not read the input of day 3, tried:\n    missing/day_03_input.txt: "
+        ));
+    }
+
+    #[test]
+    fn stores_inputs_in_the_most_preferred_dir() {
+        let dirs = InputDirs::new(vec![PathBuf::from("missing"), PathBuf::from("resources")]);
+        assert_eq!(Path::new("missing"), dirs.cache_dir());
+    }
+
+    #[test]
+    fn parses_sources() {
+        assert_eq!(Ok(InputSource::Stdin), "-".parse());
+        assert_eq!(
+            Ok(InputSource::File(PathBuf::from("inputs/day_01.txt"))),
+            "inputs/day_01.txt".parse()
+        );
+    }
+}
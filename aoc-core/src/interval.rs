@@ -0,0 +1,305 @@
+use std::collections::BTreeMap;
+use std::ops::RangeInclusive;
+
+/// Number type of the values of intervals
+pub trait Integer: Copy + Ord {
+    /// The next value, `None` after the largest
+    fn next(self) -> Option<Self>;
+
+    /// The previous value, `None` before the smallest
+    fn previous(self) -> Option<Self>;
+
+    /// Number of steps up to `other`, which is not smaller, without overflowing
+    /// for any two values of the type
+    fn steps_to(self, other: Self) -> u128;
+}
+
+macro_rules! integer {
+    ($($number:ty),*) => {
+        $(
+            impl Integer for $number {
+                fn next(self) -> Option<$number> {
+                    self.checked_add(1)
+                }
+
+                fn previous(self) -> Option<$number> {
+                    self.checked_sub(1)
+                }
+
+                fn steps_to(self, other: $number) -> u128 {
+                    // The difference is below 2^128 for every type, so the
+                    // wrapping subtraction of the sign extended values is exact
+                    (other as u128).wrapping_sub(self as u128)
+                }
+            }
+        )*
+    };
+}
+
+integer!(
+    i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize
+);
+
+/// A set of integers kept as the inclusive ranges it covers
+///
+/// Overlapping and touching ranges are merged, so iterating gives the fewest
+/// ranges covering the set, in ascending order.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct IntervalSet<T> {
+    /// End of every range by its start
+    ranges: BTreeMap<T, T>,
+}
+
+impl<T: Integer> IntervalSet<T> {
+    pub fn new() -> IntervalSet<T> {
+        IntervalSet {
+            ranges: BTreeMap::new(),
+        }
+    }
+
+    /// Adds the values of the range, merging it with the ranges it overlaps or touches
+    pub fn insert(&mut self, range: RangeInclusive<T>) {
+        let (mut start, mut end) = range.into_inner();
+        if start > end {
+            return;
+        }
+        if let Some((&before, &before_end)) = self.ranges.range(..start).next_back()
+            && before_end.next().is_none_or(|after| after >= start)
+        {
+            start = before;
+        }
+        let merged: Vec<T> = match end.next() {
+            Some(after) => self.ranges.range(start..=after).map(|(&s, _)| s).collect(),
+            None => self.ranges.range(start..).map(|(&s, _)| s).collect(),
+        };
+        for merged_start in merged {
+            end = end.max(self.ranges.remove(&merged_start).unwrap());
+        }
+        self.ranges.insert(start, end);
+    }
+
+    /// Takes the values of the range out, splitting the ranges it falls into
+    pub fn remove(&mut self, range: RangeInclusive<T>) {
+        let (start, end) = range.into_inner();
+        if start > end {
+            return;
+        }
+        let overlapping: Vec<(T, T)> = self
+            .ranges
+            .range(..=end)
+            .rev()
+            .take_while(|&(_, &e)| e >= start)
+            .map(|(&s, &e)| (s, e))
+            .collect();
+        for (s, e) in overlapping {
+            self.ranges.remove(&s);
+            if let Some(before) = start.previous().filter(|_| s < start) {
+                self.ranges.insert(s, before);
+            }
+            if let Some(after) = end.next().filter(|_| e > end) {
+                self.ranges.insert(after, e);
+            }
+        }
+    }
+
+    /// Takes all values of the other set out
+    pub fn subtract(&mut self, other: &IntervalSet<T>) {
+        for range in other.iter() {
+            self.remove(range);
+        }
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        self.ranges
+            .range(..=value)
+            .next_back()
+            .is_some_and(|(_, &end)| value <= end)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// Number of values in the set, as a `u128` so it fits for every type,
+    /// except for the one count of a set of all `u128` or `i128` values, which saturates
+    pub fn total_len(&self) -> u128 {
+        self.ranges
+            .iter()
+            .map(|(&start, &end)| start.steps_to(end).saturating_add(1))
+            .fold(0, u128::saturating_add)
+    }
+
+    /// The ranges of values within `bounds` that are not in the set, in ascending order
+    pub fn gaps(&self, bounds: RangeInclusive<T>) -> Vec<RangeInclusive<T>> {
+        let (low, high) = bounds.into_inner();
+        let mut gaps = Vec::new();
+        if low > high {
+            return gaps;
+        }
+        let first = self
+            .ranges
+            .range(..=low)
+            .next_back()
+            .map_or(low, |(&start, _)| start);
+        let mut uncovered = Some(low);
+        for (&start, &end) in self.ranges.range(first..=high) {
+            let Some(from) = uncovered else {
+                break;
+            };
+            if start > from {
+                gaps.push(from..=start.previous().unwrap());
+            }
+            if end >= from {
+                uncovered = end.next();
+            }
+        }
+        if let Some(from) = uncovered.filter(|&from| from <= high) {
+            gaps.push(from..=high);
+        }
+        gaps
+    }
+
+    /// The ranges of the set in ascending order
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = RangeInclusive<T>> + '_ {
+        self.ranges.iter().map(|(&start, &end)| start..=end)
+    }
+}
+
+/// Sorts the ranges and merges them in one go, faster than inserting them one by one
+impl<T: Integer> FromIterator<RangeInclusive<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = RangeInclusive<T>>>(ranges: I) -> IntervalSet<T> {
+        let mut ranges: Vec<(T, T)> = ranges
+            .into_iter()
+            .map(RangeInclusive::into_inner)
+            .filter(|(start, end)| start <= end)
+            .collect();
+        ranges.sort_unstable();
+        let mut merged: Vec<(T, T)> = Vec::with_capacity(ranges.len());
+        for (start, end) in ranges {
+            match merged.last_mut() {
+                Some((_, last_end)) if last_end.next().is_none_or(|after| after >= start) => {
+                    *last_end = end.max(*last_end);
+                }
+                _ => merged.push((start, end)),
+            }
+        }
+        IntervalSet {
+            ranges: merged.into_iter().collect(),
+        }
+    }
+}
+
+impl<T: Integer> Extend<RangeInclusive<T>> for IntervalSet<T> {
+    fn extend<I: IntoIterator<Item = RangeInclusive<T>>>(&mut self, ranges: I) {
+        for range in ranges {
+            self.insert(range);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use proptest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn merges_and_splits_ranges() {
+        let mut set: IntervalSet<i64> = [1..=3, 10..=12, 4..=5].into_iter().collect();
+        assert_eq!(vec![1..=5, 10..=12], set.iter().collect::<Vec<_>>());
+        assert_eq!(8, set.total_len());
+        assert!(set.contains(5) && !set.contains(6) && !set.contains(0));
+
+        set.insert(RangeInclusive::new(20, 19));
+        set.insert(6..=9);
+        assert_eq!(vec![1..=12], set.iter().collect::<Vec<_>>());
+        set.remove(4..=6);
+        assert_eq!(vec![1..=3, 7..=12], set.iter().collect::<Vec<_>>());
+        assert_eq!(vec![-2..=0, 4..=6, 13..=15], set.gaps(-2..=15));
+        assert_eq!(vec![4..=6], set.gaps(2..=8));
+        assert!(set.gaps(8..=9).is_empty());
+
+        set.subtract(&[0..=1, 12..=20].into_iter().collect());
+        assert_eq!(vec![2..=3, 7..=11], set.iter().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn handles_the_extreme_values() {
+        let mut set: IntervalSet<u8> = [0..=10, 250..=255, 11..=11].into_iter().collect();
+        assert_eq!(vec![0..=11, 250..=255], set.iter().collect::<Vec<_>>());
+        assert_eq!(vec![12..=249], set.gaps(0..=255));
+        set.remove(0..=0);
+        set.remove(255..=255);
+        assert_eq!(vec![1..=11, 250..=254], set.iter().collect::<Vec<_>>());
+
+        let all: IntervalSet<u8> = [0..=u8::MAX].into_iter().collect();
+        assert_eq!(256, all.total_len());
+        let all: IntervalSet<i64> = [i64::MIN..=i64::MAX].into_iter().collect();
+        assert_eq!(1 << 64, all.total_len());
+        let all: IntervalSet<i128> = [i128::MIN..=-1, 0..=i128::MAX].into_iter().collect();
+        assert_eq!(u128::MAX, all.total_len());
+    }
+
+    #[derive(Clone, Debug)]
+    enum Change {
+        Insert(RangeInclusive<u8>),
+        Remove(RangeInclusive<u8>),
+    }
+
+    fn change() -> impl Strategy<Value = Change> {
+        (any::<bool>(), any::<u8>(), any::<u8>()).prop_map(|(insert, a, b)| {
+            let range = a.min(b)..=a.max(b);
+            if insert {
+                Change::Insert(range)
+            } else {
+                Change::Remove(range)
+            }
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn behaves_like_a_set_of_values(changes in prop::collection::vec(change(), 0..20), low in any::<u8>(), high in any::<u8>()) {
+            let mut set = IntervalSet::new();
+            let mut values = BTreeSet::new();
+            for change in changes {
+                match change {
+                    Change::Insert(range) => {
+                        values.extend(range.clone());
+                        set.insert(range);
+                    }
+                    Change::Remove(range) => {
+                        values.retain(|value| !range.contains(value));
+                        set.remove(range);
+                    }
+                }
+            }
+
+            let ranges: Vec<_> = set.iter().collect();
+            for pair in ranges.windows(2) {
+                prop_assert!(*pair[0].end() + 1 < *pair[1].start());
+            }
+            prop_assert_eq!(values.len() as u128, set.total_len());
+            prop_assert_eq!(values.is_empty(), set.is_empty());
+            for value in 0..=u8::MAX {
+                prop_assert_eq!(values.contains(&value), set.contains(value));
+            }
+
+            let gaps: BTreeSet<u8> = set.gaps(low..=high).into_iter().flatten().collect();
+            let expected: BTreeSet<u8> = (low..=high).filter(|value| !values.contains(value)).collect();
+            prop_assert_eq!(expected, gaps);
+        }
+
+        #[test]
+        fn collects_like_inserting(ranges in prop::collection::vec((0..=u8::MAX, 0..=u8::MAX), 0..20)) {
+            let ranges = ranges.into_iter().map(|(a, b)| a..=b);
+            let mut inserted = IntervalSet::new();
+            for range in ranges.clone() {
+                inserted.insert(range);
+            }
+            prop_assert_eq!(inserted, ranges.collect::<IntervalSet<u8>>());
+        }
+    }
+}
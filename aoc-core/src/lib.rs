@@ -0,0 +1,25 @@
+pub mod answer;
+pub mod answers;
+#[cfg(feature = "bench")]
+pub mod bench;
+pub mod cli;
+pub mod config;
+pub mod disjoint_set;
+pub mod examples;
+pub mod fetch;
+pub mod grid;
+pub mod input_utils;
+pub mod interval;
+pub mod ocr;
+pub mod output;
+pub mod params;
+pub mod parse;
+pub mod perf;
+pub mod point;
+pub mod report;
+pub mod runner;
+pub mod scaffold;
+pub mod search;
+pub mod solution;
+pub mod submit;
+pub mod watch;
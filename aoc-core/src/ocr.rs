@@ -0,0 +1,147 @@
+use crate::answer::{Answer, PixelGrid};
+
+type Glyph = (char, &'static [&'static str]);
+
+/// Letters of the 6 pixel high font, each letter is 4 pixels wide except for I and Y
+const FONT_6: &[Glyph] = &[
+    ('A', &[".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+    ('B', &["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+    ('C', &[".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+    ('E', &["####", "#...", "###.", "#...", "#...", "####"]),
+    ('F', &["####", "#...", "###.", "#...", "#...", "#..."]),
+    ('G', &[".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+    ('H', &["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+    ('I', &["###", ".#.", ".#.", ".#.", ".#.", "###"]),
+    ('J', &["..##", "...#", "...#", "...#", "#..#", ".##."]),
+    ('K', &["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+    ('L', &["#...", "#...", "#...", "#...", "#...", "####"]),
+    ('O', &[".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('P', &["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+    ('R', &["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+    ('S', &[".###", "#...", "#...", ".##.", "...#", "###."]),
+    ('U', &["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('Y', &["#...#", "#...#", ".#.#.", "..#..", "..#..", "..#.."]),
+    ('Z', &["####", "...#", "..#.", ".#..", "#...", "####"]),
+];
+
+/// Letters of the 10 pixel high font, each letter is 6 pixels wide
+#[rustfmt::skip]
+const FONT_10: &[Glyph] = &[
+    ('A', &["..##..", ".#..#.", "#....#", "#....#", "#....#", "######", "#....#", "#....#", "#....#", "#....#"]),
+    ('B', &["#####.", "#....#", "#....#", "#....#", "#####.", "#....#", "#....#", "#....#", "#....#", "#####."]),
+    ('C', &[".####.", "#....#", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#....#", ".####."]),
+    ('E', &["######", "#.....", "#.....", "#.....", "#####.", "#.....", "#.....", "#.....", "#.....", "######"]),
+    ('F', &["######", "#.....", "#.....", "#.....", "#####.", "#.....", "#.....", "#.....", "#.....", "#....."]),
+    ('G', &[".####.", "#....#", "#.....", "#.....", "#.....", "#..###", "#....#", "#....#", "#...##", ".###.#"]),
+    ('H', &["#....#", "#....#", "#....#", "#....#", "######", "#....#", "#....#", "#....#", "#....#", "#....#"]),
+    ('J', &["...###", "....#.", "....#.", "....#.", "....#.", "....#.", "....#.", "#...#.", "#...#.", ".###.."]),
+    ('K', &["#....#", "#...#.", "#..#..", "#.#...", "##....", "##....", "#.#...", "#..#..", "#...#.", "#....#"]),
+    ('L', &["#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "#.....", "######"]),
+    ('N', &["#....#", "##...#", "##...#", "#.#..#", "#.#..#", "#..#.#", "#..#.#", "#...##", "#...##", "#....#"]),
+    ('P', &["#####.", "#....#", "#....#", "#....#", "#####.", "#.....", "#.....", "#.....", "#.....", "#....."]),
+    ('R', &["#####.", "#....#", "#....#", "#....#", "#####.", "#..#..", "#...#.", "#...#.", "#....#", "#....#"]),
+    ('X', &["#....#", "#....#", ".#..#.", ".#..#.", "..##..", "..##..", ".#..#.", ".#..#.", "#....#", "#....#"]),
+    ('Z', &["######", ".....#", ".....#", "....#.", "...#..", "..#...", ".#....", "#.....", "#.....", "######"]),
+];
+
+/// Reads the capital letters drawn in the Advent of Code block font
+///
+/// Letters are separated by blank columns and may be surrounded by blank
+/// margins. Returns `None` when the height matches neither font or any of the
+/// letters is not known.
+pub fn recognize(grid: &PixelGrid) -> Option<String> {
+    let lit_rows: Vec<usize> = (0..grid.height())
+        .filter(|&y| (0..grid.width()).any(|x| grid.is_lit(x, y)))
+        .collect();
+    let (&top, &bottom) = (lit_rows.first()?, lit_rows.last()?);
+    let font = match bottom - top + 1 {
+        6 => FONT_6,
+        10 => FONT_10,
+        _ => return None,
+    };
+
+    let is_blank_column = |x: usize| (top..=bottom).all(|y| !grid.is_lit(x, y));
+    let mut letters = String::new();
+    let mut x = 0;
+    while x < grid.width() {
+        if is_blank_column(x) {
+            x += 1;
+            continue;
+        }
+        let start = x;
+        while x < grid.width() && !is_blank_column(x) {
+            x += 1;
+        }
+        let glyph: Vec<String> = (top..=bottom)
+            .map(|y| {
+                (start..x)
+                    .map(|x| if grid.is_lit(x, y) { '#' } else { '.' })
+                    .collect()
+            })
+            .collect();
+        let &(letter, _) = font.iter().find(|(_, rows)| glyph == *rows)?;
+        letters.push(letter);
+    }
+    Some(letters)
+}
+
+/// Replaces a grid answer with the letters it spells, other answers are kept as they are
+pub fn recognize_answer(answer: Answer) -> Answer {
+    match &answer {
+        Answer::Grid(grid) => recognize(grid).map_or(answer, Answer::Text),
+        _ => answer,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recognizes_small_letters() {
+        let grid = PixelGrid::parse(
+            "
+            ###..#....###...##..####.###...##..#....
+            #..#.#....#..#.#..#.#....#..#.#..#.#....
+            #..#.#....#..#.#..#.###..###..#....#....
+            ###..#....###..####.#....#..#.#....#....
+            #....#....#....#..#.#....#..#.#..#.#....
+            #....####.#....#..#.#....###...##..####.",
+        );
+        assert_eq!(Some(String::from("PLPAFBCL")), recognize(&grid));
+        assert_eq!(
+            Answer::from("PLPAFBCL"),
+            recognize_answer(Answer::from(grid))
+        );
+    }
+
+    #[test]
+    fn recognizes_large_letters_with_margin() {
+        let grid = PixelGrid::parse(
+            "
+            .................
+            .#....#..######..
+            .#....#.......#..
+            ..#..#........#..
+            ..#..#.......#...
+            ...##.......#....
+            ...##......#.....
+            ..#..#....#......
+            ..#..#...#.......
+            .#....#..#.......
+            .#....#..######..
+            .................",
+        );
+        assert_eq!(Some(String::from("XZ")), recognize(&grid));
+    }
+
+    #[test]
+    fn keeps_unknown_drawings() {
+        let grid = PixelGrid::parse("##..##\n..##..\n##..##\n..##..\n##..##\n..##..");
+        assert_eq!(None, recognize(&grid));
+        assert_eq!(
+            Answer::from(grid.clone()),
+            recognize_answer(Answer::from(grid))
+        );
+    }
+}
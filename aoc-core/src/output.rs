@@ -0,0 +1,493 @@
+use std::time::Duration;
+
+use serde_json::{Map, Value, json};
+
+use crate::answer::Answer;
+use crate::answers::{Check, Verdict};
+use crate::perf::{Comparison, Record, Trend};
+use crate::runner::{DayReport, Outcome};
+use crate::solution::{Part, Run};
+
+/// Error of the parts of a day without an input
+const MISSING_INPUT: &str = "missing input";
+
+/// How the results of solving are printed
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum Format {
+    /// Lines and tables meant to be read by people
+    #[default]
+    Text,
+    /// A single JSON object with a result per part
+    Json,
+    /// A header line and a line per part
+    Csv,
+}
+
+/// Outcome of a single part, the unit of the machine readable formats
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PartResult {
+    pub day: u8,
+    pub title: &'static str,
+    pub part: Part,
+    pub answer: Option<Answer>,
+    /// Time spent parsing the input the part was solved from
+    pub parse_duration: Option<Duration>,
+    pub duration: Option<Duration>,
+    pub error: Option<String>,
+    /// The verdict and the accepted answer, only when verifying
+    pub check: Option<(Verdict, Option<Answer>)>,
+}
+
+impl PartResult {
+    /// A part that could not be solved
+    pub fn failed(
+        day: u8,
+        title: &'static str,
+        part: Part,
+        error: impl Into<String>,
+    ) -> PartResult {
+        PartResult {
+            day,
+            title,
+            part,
+            answer: None,
+            parse_duration: None,
+            duration: None,
+            error: Some(error.into()),
+            check: None,
+        }
+    }
+}
+
+/// A result per solved part of the run
+pub fn run_results(day: u8, title: &'static str, run: &Run) -> Vec<PartResult> {
+    run.parts
+        .iter()
+        .map(|part_run| PartResult {
+            day,
+            title,
+            part: part_run.part,
+            answer: Some(part_run.answer.clone()),
+            parse_duration: Some(run.parse_duration),
+            duration: Some(part_run.duration),
+            error: None,
+            check: None,
+        })
+        .collect()
+}
+
+/// A result for both parts of every day, with the reason parts could not be solved
+pub fn results(reports: &[DayReport]) -> Vec<PartResult> {
+    reports
+        .iter()
+        .flat_map(|report| match &report.outcome {
+            Outcome::Solved(run) => {
+                let mut results = run_results(report.day, report.title, run);
+                results.extend(report.failures.iter().map(|(part, failure)| {
+                    PartResult::failed(report.day, report.title, *part, failure.to_string())
+                }));
+                results.sort_by_key(|result| result.part);
+                results
+            }
+            Outcome::ParseFailed(error) => Part::BOTH
+                .map(|part| PartResult::failed(report.day, report.title, part, error.to_string()))
+                .to_vec(),
+            Outcome::ParseAborted(failure) => Part::BOTH
+                .map(|part| {
+                    let error = format!("parsing {failure}");
+                    PartResult::failed(report.day, report.title, part, error)
+                })
+                .to_vec(),
+            Outcome::MissingInput => Part::BOTH
+                .map(|part| PartResult::failed(report.day, report.title, part, MISSING_INPUT))
+                .to_vec(),
+        })
+        .collect()
+}
+
+/// Adds the verdict and the accepted answer of the check of every part
+pub fn check_results(results: &mut [PartResult], checks: &[Check]) {
+    for result in results {
+        result.check = checks
+            .iter()
+            .find(|check| check.day == result.day && check.part == result.part)
+            .map(|check| (check.verdict, check.expected.clone()));
+    }
+}
+
+/// Name of the kind of answer, `number`, `text` or `grid`
+fn answer_type(answer: &Answer) -> &'static str {
+    match answer {
+        Answer::Signed(_) | Answer::Unsigned(_) | Answer::Big(_) => "number",
+        Answer::Text(_) => "text",
+        Answer::Grid(_) => "grid",
+    }
+}
+
+fn verdict_name(verdict: Verdict) -> &'static str {
+    match verdict {
+        Verdict::Pass => "pass",
+        Verdict::Wrong => "wrong",
+        Verdict::ParseFailed => "parse_failed",
+        Verdict::Panicked => "panicked",
+        Verdict::TimedOut => "timed_out",
+        Verdict::NotImplemented => "not_implemented",
+        Verdict::Missing => "missing",
+    }
+}
+
+const RESULT_FIELDS: [&str; 8] = [
+    "day",
+    "title",
+    "part",
+    "answer",
+    "answer_type",
+    "parse_ns",
+    "solve_ns",
+    "error",
+];
+const CHECK_FIELDS: [&str; 2] = ["verdict", "expected"];
+
+/// Values of the fields of a result, in the order of [`RESULT_FIELDS`] followed
+/// by [`CHECK_FIELDS`] when verifying
+///
+/// Answers are strings as large numbers may not fit a JSON number.
+fn values(result: &PartResult) -> Vec<Value> {
+    let nanos = |duration: Option<Duration>| json!(duration.map(|d| d.as_nanos() as u64));
+    let mut values = vec![
+        json!(result.day),
+        json!(result.title),
+        json!(result.part.number()),
+        json!(result.answer.as_ref().map(Answer::to_string)),
+        json!(result.answer.as_ref().map(answer_type)),
+        nanos(result.parse_duration),
+        nanos(result.duration),
+        json!(result.error),
+    ];
+    if let Some((verdict, expected)) = &result.check {
+        values.push(json!(verdict_name(*verdict)));
+        values.push(json!(expected.as_ref().map(Answer::to_string)));
+    }
+    values
+}
+
+/// An object holding the year and an array of results
+///
+/// ```json
+/// {"year": 2022, "results": [{"day": 1, "title": "Calorie Counting", "part": 1,
+///   "answer": "70116", "answer_type": "number", "parse_ns": 126900,
+///   "solve_ns": 3000, "error": null}]}
+/// ```
+pub fn json(year: u16, results: &[PartResult]) -> String {
+    let fields: Vec<&str> = RESULT_FIELDS.iter().chain(&CHECK_FIELDS).copied().collect();
+    json_rows(year, "results", &fields, results.iter().map(values))
+}
+
+/// A header line and a line per result, missing values are empty
+pub fn csv(year: u16, results: &[PartResult]) -> String {
+    let mut fields = RESULT_FIELDS.to_vec();
+    if results.iter().any(|result| result.check.is_some()) {
+        fields.extend(CHECK_FIELDS);
+    }
+    csv_rows(year, &fields, results.iter().map(values))
+}
+
+const RECORD_FIELDS: [&str; 6] = [
+    "timestamp",
+    "commit",
+    "day",
+    "part",
+    "median_ns",
+    "peak_bytes",
+];
+
+fn record_values(record: &Record) -> Vec<Value> {
+    vec![
+        json!(record.timestamp),
+        json!(record.commit),
+        json!(record.day),
+        json!(record.part.number()),
+        json!(record.median.as_nanos() as u64),
+        json!(record.peak_memory),
+    ]
+}
+
+/// The recorded timings as an object holding the year and an array of records,
+/// with the fields of the lines of the history
+pub fn records_json(year: u16, records: &[Record]) -> String {
+    json_rows(
+        year,
+        "records",
+        &RECORD_FIELDS,
+        records.iter().map(record_values),
+    )
+}
+
+/// The recorded timings as a header line and a line per record
+pub fn records_csv(year: u16, records: &[Record]) -> String {
+    csv_rows(year, &RECORD_FIELDS, records.iter().map(record_values))
+}
+
+const COMPARISON_FIELDS: [&str; 10] = [
+    "day",
+    "part",
+    "commit",
+    "median_ns",
+    "peak_bytes",
+    "baseline_commit",
+    "baseline_ns",
+    "change_percent",
+    "threshold_percent",
+    "trend",
+];
+
+fn trend_name(trend: Trend) -> &'static str {
+    match trend {
+        Trend::Regressed => "regressed",
+        Trend::Improved => "improved",
+        Trend::Unchanged => "unchanged",
+        Trend::New => "new",
+    }
+}
+
+/// Values of the fields of a comparison, in the order of [`COMPARISON_FIELDS`]
+fn comparison_values(comparison: &Comparison, threshold: f64) -> Vec<Value> {
+    let latest = &comparison.latest;
+    let baseline = comparison.baseline.as_ref();
+    vec![
+        json!(latest.day),
+        json!(latest.part.number()),
+        json!(latest.commit),
+        json!(latest.median.as_nanos() as u64),
+        json!(latest.peak_memory),
+        json!(baseline.map(|baseline| &baseline.commit)),
+        json!(baseline.map(|baseline| baseline.median.as_nanos() as u64)),
+        json!(comparison.change),
+        json!(threshold),
+        json!(trend_name(comparison.trend)),
+    ]
+}
+
+/// The comparisons of `perf report` as an object holding the year and an
+/// array of comparisons
+///
+/// ```json
+/// {"year": 2022, "comparisons": [{"day": 12, "part": 1, "commit": "f60e0a7",
+///   "median_ns": 35000000, "peak_bytes": 2048, "baseline_commit": "947febb",
+///   "baseline_ns": 71000000, "change_percent": -50.7, "threshold_percent": 10.0,
+///   "trend": "improved"}]}
+/// ```
+pub fn comparisons_json(year: u16, comparisons: &[Comparison], threshold: f64) -> String {
+    let rows = comparisons
+        .iter()
+        .map(|comparison| comparison_values(comparison, threshold));
+    json_rows(year, "comparisons", &COMPARISON_FIELDS, rows)
+}
+
+/// The comparisons of `perf report` as a header line and a line per part
+pub fn comparisons_csv(year: u16, comparisons: &[Comparison], threshold: f64) -> String {
+    let rows = comparisons
+        .iter()
+        .map(|comparison| comparison_values(comparison, threshold));
+    csv_rows(year, &COMPARISON_FIELDS, rows)
+}
+
+/// An object holding the year and under `key` an object per row with the
+/// values of the `fields`
+fn json_rows(
+    year: u16,
+    key: &str,
+    fields: &[&str],
+    rows: impl Iterator<Item = Vec<Value>>,
+) -> String {
+    let rows: Vec<Value> = rows
+        .map(|values| {
+            let object: Map<String, Value> = fields
+                .iter()
+                .map(|field| field.to_string())
+                .zip(values)
+                .collect();
+            Value::Object(object)
+        })
+        .collect();
+    let mut output = Map::new();
+    output.insert(String::from("year"), json!(year));
+    output.insert(key.to_string(), Value::Array(rows));
+    Value::Object(output).to_string()
+}
+
+/// A header line with the year and the `fields`, and a line per row, missing
+/// values are empty
+fn csv_rows(year: u16, fields: &[&str], rows: impl Iterator<Item = Vec<Value>>) -> String {
+    let mut header = vec!["year"];
+    header.extend(fields);
+    let mut lines = vec![header.join(",")];
+    for values in rows {
+        let mut cells = vec![year.to_string()];
+        cells.extend(values.into_iter().map(|value| match value {
+            Value::Null => String::new(),
+            Value::String(text) => csv_cell(&text),
+            value => value.to_string(),
+        }));
+        lines.push(cells.join(","));
+    }
+    lines.join("\n")
+}
+
+/// Quotes a cell holding a separator, a quote or a line break
+fn csv_cell(text: &str) -> String {
+    if text.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::answer::PixelGrid;
+    use crate::answers::{self, ExpectedAnswers};
+    use crate::runner::Failure;
+    use crate::solution::PartRun;
+
+    fn run() -> Run {
+        Run {
+            parse_duration: Duration::from_micros(2),
+            parts: vec![
+                PartRun {
+                    part: Part::First,
+                    answer: Answer::from(42),
+                    duration: Duration::from_nanos(1500),
+                },
+                PartRun {
+                    part: Part::Second,
+                    answer: Answer::from("A,B"),
+                    duration: Duration::from_nanos(700),
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn writes_json() {
+        let mut results = run_results(1, "First", &run());
+        results.push(PartResult::failed(
+            2,
+            "Second",
+            Part::First,
+            "missing input",
+        ));
+        let value: Value = serde_json::from_str(&json(2022, &results)).unwrap();
+        assert_eq!(
+            json!({
+                "year": 2022,
+                "results": [
+                    {"day": 1, "title": "First", "part": 1, "answer": "42", "answer_type": "number",
+                     "parse_ns": 2000, "solve_ns": 1500, "error": null},
+                    {"day": 1, "title": "First", "part": 2, "answer": "A,B", "answer_type": "text",
+                     "parse_ns": 2000, "solve_ns": 700, "error": null},
+                    {"day": 2, "title": "Second", "part": 1, "answer": null, "answer_type": null,
+                     "parse_ns": null, "solve_ns": null, "error": "missing input"},
+                ]
+            }),
+            value
+        );
+    }
+
+    #[test]
+    fn writes_csv_with_verdicts() {
+        let mut solved = run();
+        solved.parts[0].answer = Answer::from(PixelGrid::parse("#.\n.#"));
+        let reports = [DayReport {
+            day: 1,
+            title: "First",
+            outcome: Outcome::Solved(solved),
+            failures: Vec::new(),
+        }];
+        let mut checked = results(&reports);
+        let expected = ExpectedAnswers::parse("[1]\npart2 = \"A,B\"").unwrap();
+        check_results(&mut checked, &answers::verify(&reports, &expected));
+        assert_eq!(
+            "year,day,title,part,answer,answer_type,parse_ns,solve_ns,error,verdict,expected\n\
+             2022,1,First,1,\"#.\n.#\",grid,2000,1500,,missing,\n\
+             2022,1,First,2,\"A,B\",text,2000,700,,pass,\"A,B\"",
+            csv(2022, &checked)
+        );
+
+        let reports = [DayReport {
+            day: 2,
+            title: "Second",
+            outcome: Outcome::Solved(Run {
+                parts: vec![],
+                ..run()
+            }),
+            failures: vec![
+                (Part::Second, Failure::TimedOut(Duration::from_secs(5))),
+                (Part::First, Failure::NotImplemented),
+            ],
+        }];
+        let mut checked = results(&reports);
+        check_results(&mut checked, &answers::verify(&reports, &expected));
+        assert_eq!(
+            "year,day,title,part,answer,answer_type,parse_ns,solve_ns,error,verdict,expected\n\
+             2022,2,Second,1,,,,,not implemented,not_implemented,\n\
+             2022,2,Second,2,,,,,timed out after 5s,timed_out,",
+            csv(2022, &checked)
+        );
+        assert_eq!(
+            "year,day,title,part,answer,answer_type,parse_ns,solve_ns,error",
+            csv(2022, &[])
+        );
+    }
+
+    #[test]
+    fn writes_perf_records_and_comparisons() {
+        let record = |commit: &str, millis| Record {
+            timestamp: 1_734_393_600,
+            commit: commit.to_string(),
+            day: 12,
+            part: Part::First,
+            median: Duration::from_millis(millis),
+            peak_memory: Some(2048),
+        };
+        assert_eq!(
+            "year,timestamp,commit,day,part,median_ns,peak_bytes\n\
+             2022,1734393600,947febb,12,1,80000000,2048",
+            records_csv(2022, &[record("947febb", 80)])
+        );
+
+        let comparisons = [
+            Comparison {
+                latest: record("f60e0a7", 40),
+                baseline: Some(record("947febb", 80)),
+                change: Some(-50.0),
+                trend: Trend::Improved,
+            },
+            Comparison {
+                latest: Record {
+                    part: Part::Second,
+                    peak_memory: None,
+                    ..record("f60e0a7", 5)
+                },
+                baseline: None,
+                change: None,
+                trend: Trend::New,
+            },
+        ];
+        let value: Value =
+            serde_json::from_str(&comparisons_json(2022, &comparisons, 10.0)).unwrap();
+        assert_eq!(
+            json!({"day": 12, "part": 1, "commit": "f60e0a7", "median_ns": 40000000,
+                   "peak_bytes": 2048, "baseline_commit": "947febb", "baseline_ns": 80000000,
+                   "change_percent": -50.0, "threshold_percent": 10.0, "trend": "improved"}),
+            value["comparisons"][0]
+        );
+        assert_eq!(
+            "year,day,part,commit,median_ns,peak_bytes,baseline_commit,baseline_ns,change_percent,threshold_percent,trend\n\
+             2022,12,1,f60e0a7,40000000,2048,947febb,80000000,-50.0,10.0,improved\n\
+             2022,12,2,f60e0a7,5000000,,,,,10.0,new",
+            comparisons_csv(2022, &comparisons, 10.0)
+        );
+    }
+}
@@ -0,0 +1,217 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// A number a solution can be tuned with, e.g. the row to inspect
+///
+/// The puzzle descriptions often use smaller numbers for their examples than
+/// for the real input, the default is the one for the real input.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Param {
+    pub name: &'static str,
+    pub default: i64,
+    pub description: &'static str,
+    /// Smallest value the type the param is declared with can hold
+    pub min: i64,
+    /// Largest value the type the param is declared with can hold
+    pub max: i64,
+}
+
+impl Param {
+    /// A param the solution reads as a `T`, e.g. `Param::new::<u32>(...)`
+    ///
+    /// Overrides that don't fit in a `T` are rejected when resolving them.
+    pub const fn new<T: ParamType>(
+        name: &'static str,
+        default: i64,
+        description: &'static str,
+    ) -> Param {
+        assert!(
+            T::MIN <= default && default <= T::MAX,
+            "The default of a param does not fit its type"
+        );
+        Param {
+            name,
+            default,
+            description,
+            min: T::MIN,
+            max: T::MAX,
+        }
+    }
+}
+
+/// An integer type a [`Param`] can be declared with
+pub trait ParamType {
+    const MIN: i64;
+    const MAX: i64;
+}
+
+macro_rules! param_types {
+    ($($t:ty),*) => {
+        $(
+            impl ParamType for $t {
+                const MIN: i64 = saturate(<$t>::MIN as i128);
+                const MAX: i64 = saturate(<$t>::MAX as i128);
+            }
+        )*
+    };
+}
+
+param_types!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+/// The value limited to what an `i64` can hold
+const fn saturate(value: i128) -> i64 {
+    if value > i64::MAX as i128 {
+        i64::MAX
+    } else if value < i64::MIN as i128 {
+        i64::MIN
+    } else {
+        value as i64
+    }
+}
+
+impl fmt::Display for Param {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} = {}", self.name, self.default)
+    }
+}
+
+/// Values of named params
+///
+/// Either overrides given by the user or, once resolved against the declared
+/// [`Param`]s of a solution, the values to solve with.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Params {
+    values: BTreeMap<String, i64>,
+}
+
+impl Params {
+    /// The default values of the declared params
+    pub fn defaults(declared: &[Param]) -> Params {
+        let mut params = Params::default();
+        for param in declared {
+            params.set(param.name, param.default);
+        }
+        params
+    }
+
+    /// The default values of the declared params with `overrides` applied
+    ///
+    /// Fails when one of the overrides is not declared or does not fit the
+    /// type of its param.
+    pub fn resolve(declared: &[Param], overrides: &Params) -> Result<Params, String> {
+        let mut params = Params::defaults(declared);
+        for (name, &value) in &overrides.values {
+            let Some(param) = declared.iter().find(|param| param.name == name) else {
+                let names: Vec<&str> = declared.iter().map(|param| param.name).collect();
+                return Err(if names.is_empty() {
+                    format!("Unknown param `{name}`, there are no params")
+                } else {
+                    format!("Unknown param `{name}`, expected {}", names.join(", "))
+                });
+            };
+            if !(param.min..=param.max).contains(&value) {
+                return Err(format!(
+                    "Param `{name}` must be between {} and {}, not {value}",
+                    param.min, param.max
+                ));
+            }
+            params.set(name.as_str(), value);
+        }
+        Ok(params)
+    }
+
+    /// Value of a declared param converted to the type the solution works with
+    ///
+    /// Panics when the param is not declared or is read as a smaller type than
+    /// it was declared with.
+    pub fn get<T: TryFrom<i64>>(&self, name: &str) -> T {
+        let value = *self
+            .values
+            .get(name)
+            .unwrap_or_else(|| panic!("Param `{name}` is not declared"));
+        T::try_from(value).unwrap_or_else(|_| panic!("Param `{name}` is out of range: {value}"))
+    }
+
+    pub fn set(&mut self, name: impl Into<String>, value: i64) {
+        self.values.insert(name.into(), value);
+    }
+}
+
+/// Parses a `name=value` assignment of a param, as given on the command line
+pub fn parse_assignment(assignment: &str) -> Result<(String, i64), String> {
+    let (name, value) = assignment
+        .split_once('=')
+        .ok_or_else(|| format!("Expected `name=value` instead of `{assignment}`"))?;
+    let value = value
+        .trim()
+        .replace('_', "")
+        .parse()
+        .map_err(|_| format!("Expected a number as value of `{}`", name.trim()))?;
+    Ok((name.trim().to_string(), value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PARAMS: &[Param] = &[
+        Param::new::<i64>("row", 2_000_000, "Row to inspect"),
+        Param::new::<usize>("count", 1000, "Connections to make"),
+    ];
+
+    #[test]
+    fn resolves_overrides() {
+        let mut overrides = Params::default();
+        overrides.set("row", 10);
+        let params = Params::resolve(PARAMS, &overrides).unwrap();
+        assert_eq!(10, params.get::<i64>("row"));
+        assert_eq!(1000, params.get::<usize>("count"));
+
+        overrides.set("bound", 20);
+        assert_eq!(
+            Err(String::from("Unknown param `bound`, expected row, count")),
+            Params::resolve(PARAMS, &overrides)
+        );
+    }
+
+    #[test]
+    fn rejects_overrides_that_do_not_fit() {
+        let mut overrides = Params::default();
+        overrides.set("count", -1);
+        assert_eq!(
+            Err(format!(
+                "Param `count` must be between 0 and {}, not -1",
+                i64::MAX
+            )),
+            Params::resolve(PARAMS, &overrides)
+        );
+
+        const SMALL: &[Param] = &[Param::new::<u8>("minutes", 30, "Minutes left")];
+        overrides = Params::default();
+        overrides.set("minutes", 256);
+        assert_eq!(
+            Err(String::from(
+                "Param `minutes` must be between 0 and 255, not 256"
+            )),
+            Params::resolve(SMALL, &overrides)
+        );
+        overrides.set("minutes", 255);
+        assert_eq!(
+            255,
+            Params::resolve(SMALL, &overrides)
+                .unwrap()
+                .get::<u8>("minutes")
+        );
+    }
+
+    #[test]
+    fn parses_assignments() {
+        assert_eq!(Ok((String::from("row"), 10)), parse_assignment("row=10"));
+        assert_eq!(
+            Ok((String::from("bound"), 4_000_000)),
+            parse_assignment("bound = 4_000_000")
+        );
+        assert!(parse_assignment("row").is_err());
+        assert!(parse_assignment("row=ten").is_err());
+    }
+}
@@ -0,0 +1,146 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+/// Error raised when the puzzle input does not have the expected format
+///
+/// Points at the offending part of the input, so it can be shown to the user
+/// with a caret underneath, see [`ParseError::render`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    /// Day of the puzzle, filled in by the runner and 0 until then
+    pub day: u8,
+    /// Line of the input, starting from 1
+    pub line: usize,
+    /// Column within the line in characters, starting from 1
+    pub column: usize,
+    /// The whole offending line, without the line break
+    pub text: String,
+    /// Number of characters to underline, starting at the column
+    pub width: usize,
+    pub message: String,
+}
+
+impl ParseError {
+    /// Creates an error pointing at `fragment`, which has to be a slice of `input`
+    ///
+    /// Fragments that are not part of the input are reported at its end.
+    pub fn at(input: &str, fragment: &str, message: impl Into<String>) -> ParseError {
+        let start = (fragment.as_ptr() as usize)
+            .checked_sub(input.as_ptr() as usize)
+            .filter(|offset| offset + fragment.len() <= input.len())
+            .unwrap_or(input.len());
+        let line_start = input[..start].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = input[start..].find('\n').map_or(input.len(), |i| start + i);
+        let text = input[line_start..line_end].trim_end_matches('\r');
+        let column = input[line_start..start].chars().count() + 1;
+        let width = input[start..line_end.max(start)]
+            .chars()
+            .count()
+            .min(fragment.chars().count())
+            .max(1);
+
+        ParseError {
+            day: 0,
+            line: input[..start].matches('\n').count() + 1,
+            column,
+            text: text.to_string(),
+            width,
+            message: message.into(),
+        }
+    }
+
+    /// Creates an error for input that ends before everything expected was read
+    pub fn at_end(input: &str, message: impl Into<String>) -> ParseError {
+        let trimmed = input.trim_end();
+        ParseError::at(input, &trimmed[trimmed.len()..], message)
+    }
+
+    pub fn in_day(self, day: u8) -> ParseError {
+        ParseError { day, ..self }
+    }
+
+    /// Multi line diagnostic showing the offending line with a caret underneath
+    pub fn render(&self) -> String {
+        let number = self.line.to_string();
+        let gutter = " ".repeat(number.len());
+        format!(
+            "error: {}\n{gutter}--> day {}, line {}, column {}\n{gutter} |\n{number} | {}\n{gutter} | {}{}",
+            self.message,
+            self.day,
+            self.line,
+            self.column,
+            self.text,
+            " ".repeat(self.column - 1),
+            "^".repeat(self.width),
+        )
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "day {}, line {}, column {}: {}",
+            self.day, self.line, self.column, self.message
+        )
+    }
+}
+
+impl Error for ParseError {}
+
+/// Parses `fragment` of `input` as a number
+pub fn number<T: FromStr>(input: &str, fragment: &str) -> Result<T, ParseError> {
+    fragment.parse().map_err(|_| {
+        ParseError::at(
+            input,
+            fragment,
+            format!("Expected a number, found `{fragment}`"),
+        )
+    })
+}
+
+/// Splits `fragment` of `input` around the first occurrence of `delimiter`
+pub fn split_once<'a>(
+    input: &str,
+    fragment: &'a str,
+    delimiter: &str,
+) -> Result<(&'a str, &'a str), ParseError> {
+    fragment.split_once(delimiter).ok_or_else(|| {
+        ParseError::at(
+            input,
+            fragment,
+            format!("Expected `{delimiter}` in `{fragment}`"),
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static INPUT: &str = "2-4,6-8\n2-3,4-x5\n";
+
+    #[test]
+    fn locates_fragment() {
+        let fragment = &INPUT[14..16];
+        let error = ParseError::at(INPUT, fragment, "Expected a number").in_day(4);
+        assert_eq!(
+            (4, 2, 7, 2),
+            (error.day, error.line, error.column, error.width)
+        );
+        assert_eq!("2-3,4-x5", error.text);
+        assert_eq!(
+            "error: Expected a number\n --> day 4, line 2, column 7\n  |\n2 | 2-3,4-x5\n  |       ^^",
+            error.render()
+        );
+    }
+
+    #[test]
+    fn reports_unknown_fragment_at_end() {
+        let error = number::<u32>(INPUT, &String::from("x")).unwrap_err();
+        assert_eq!((3, 1, ""), (error.line, error.column, error.text.as_str()));
+        let error = ParseError::at_end(INPUT, "Unexpected end of input");
+        assert_eq!((2, 9), (error.line, error.column));
+    }
+}
@@ -0,0 +1,412 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::collections::BTreeMap;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::Path;
+use std::process::Command;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::parse::ParseError;
+use crate::solution::{Part, Puzzle};
+
+/// Name of the file in the resources directory of a year with the timings
+/// recorded so far, one line per part and recording
+pub const HISTORY_FILE: &str = "perf_history.csv";
+const HISTORY_HEADER: &str = "timestamp,commit,day,part,median_ns,peak_bytes";
+
+static INSTALLED: AtomicBool = AtomicBool::new(false);
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+/// Allocator keeping track of the peak of allocated memory, so recordings
+/// include the memory a part needs
+///
+/// ```ignore
+/// #[global_allocator]
+/// static ALLOCATOR: aoc_core::perf::PeakAllocator = aoc_core::perf::PeakAllocator;
+/// ```
+pub struct PeakAllocator;
+
+unsafe impl GlobalAlloc for PeakAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc(layout) };
+        if !ptr.is_null() {
+            grow(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc_zeroed(layout) };
+        if !ptr.is_null() {
+            grow(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = unsafe { System.realloc(ptr, layout, new_size) };
+        if !new_ptr.is_null() {
+            if new_size > layout.size() {
+                grow(new_size - layout.size());
+            } else {
+                CURRENT.fetch_sub(layout.size() - new_size, Ordering::Relaxed);
+            }
+        }
+        new_ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) };
+        CURRENT.fetch_sub(layout.size(), Ordering::Relaxed);
+    }
+}
+
+fn grow(size: usize) {
+    INSTALLED.store(true, Ordering::Relaxed);
+    let current = CURRENT.fetch_add(size, Ordering::Relaxed) + size;
+    PEAK.fetch_max(current, Ordering::Relaxed);
+}
+
+/// Runs `f` and returns the peak of memory it allocated on top of what was
+/// allocated before, `None` unless the [`PeakAllocator`] is installed
+pub fn measure_peak<T>(f: impl FnOnce() -> T) -> (T, Option<u64>) {
+    let start = CURRENT.load(Ordering::Relaxed);
+    PEAK.store(start, Ordering::Relaxed);
+    let result = f();
+    let peak = PEAK.load(Ordering::Relaxed).saturating_sub(start);
+    (
+        result,
+        INSTALLED.load(Ordering::Relaxed).then_some(peak as u64),
+    )
+}
+
+/// Median time and peak memory of solving a part several times
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Measurement {
+    pub median: Duration,
+    pub peak_memory: Option<u64>,
+}
+
+/// Parses the input and solves `part` from it `runs` times
+///
+/// Only the time of the part counts, the peak memory includes the parsed input.
+pub fn measure(
+    puzzle: &Puzzle,
+    input: &str,
+    part: Part,
+    runs: usize,
+) -> Result<Measurement, ParseError> {
+    let mut durations = Vec::with_capacity(runs);
+    let mut peak_memory = None;
+    for _ in 0..runs.max(1) {
+        let (run, peak) = measure_peak(|| puzzle.run(input, &[part]));
+        durations.push(run?.parts[0].duration);
+        peak_memory = peak_memory.max(peak);
+    }
+    Ok(Measurement {
+        median: median(&mut durations),
+        peak_memory,
+    })
+}
+
+fn median(durations: &mut [Duration]) -> Duration {
+    durations.sort();
+    let middle = durations.len() / 2;
+    if durations.len().is_multiple_of(2) {
+        (durations[middle - 1] + durations[middle]) / 2
+    } else {
+        durations[middle]
+    }
+}
+
+/// Short hash of the checked out commit, with `-dirty` appended when there
+/// are uncommitted changes, `unknown` outside of a git repository
+pub fn current_commit() -> String {
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .args(args)
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+    };
+    match git(&["rev-parse", "--short", "HEAD"]) {
+        Some(hash) => match git(&["status", "--porcelain", "--untracked-files=no"]) {
+            Some(changes) if !changes.is_empty() => format!("{hash}-dirty"),
+            _ => hash,
+        },
+        None => String::from("unknown"),
+    }
+}
+
+/// Seconds since the Unix epoch, recorded with every timing
+pub fn timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |since| since.as_secs())
+}
+
+/// A recorded timing of a part
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Record {
+    pub timestamp: u64,
+    pub commit: String,
+    pub day: u8,
+    pub part: Part,
+    pub median: Duration,
+    pub peak_memory: Option<u64>,
+}
+
+impl Record {
+    fn to_line(&self) -> String {
+        format!(
+            "{},{},{},{},{},{}",
+            self.timestamp,
+            self.commit,
+            self.day,
+            self.part,
+            self.median.as_nanos(),
+            self.peak_memory
+                .map_or(String::new(), |peak| peak.to_string())
+        )
+    }
+
+    fn parse(line: &str) -> Option<Record> {
+        let fields: Vec<&str> = line.split(',').collect();
+        let [timestamp, commit, day, part, median, peak] = fields[..] else {
+            return None;
+        };
+        Some(Record {
+            timestamp: timestamp.parse().ok()?,
+            commit: commit.to_string(),
+            day: day.parse().ok()?,
+            part: Part::from_number(part.parse().ok()?)?,
+            median: Duration::from_nanos(median.parse().ok()?),
+            peak_memory: match peak {
+                "" => None,
+                peak => Some(peak.parse().ok()?),
+            },
+        })
+    }
+}
+
+/// Every recorded timing, stored as CSV in the order of recording:
+///
+/// ```text
+/// timestamp,commit,day,part,median_ns,peak_bytes
+/// 1734393600,947febb,12,1,71042311,1835008
+/// ```
+#[derive(Debug, Default)]
+pub struct History {
+    records: Vec<Record>,
+}
+
+impl History {
+    /// Reads the history at `path`, a missing file means nothing was recorded yet
+    pub fn load(path: &Path) -> Result<History, String> {
+        match fs::read_to_string(path) {
+            Ok(text) => History::parse(&text)
+                .map_err(|error| format!("Invalid history {}: {error}", path.display())),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(History::default()),
+            Err(error) => Err(format!("Could not read {}: {error}", path.display())),
+        }
+    }
+
+    pub fn parse(text: &str) -> Result<History, String> {
+        let records = text
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty() && *line != HISTORY_HEADER)
+            .map(|(index, line)| {
+                Record::parse(line).ok_or_else(|| format!("line {} is malformed", index + 1))
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(History { records })
+    }
+
+    /// Appends records to the history at `path`, creating it with a header if needed
+    pub fn append(path: &Path, records: &[Record]) -> Result<(), String> {
+        let mut text = String::new();
+        if !path.exists() {
+            text.push_str(HISTORY_HEADER);
+            text.push('\n');
+        }
+        for record in records {
+            text.push_str(&record.to_line());
+            text.push('\n');
+        }
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .and_then(|mut file| file.write_all(text.as_bytes()))
+            .map_err(|error| format!("Could not write {}: {error}", path.display()))
+    }
+
+    pub fn records(&self) -> &[Record] {
+        &self.records
+    }
+}
+
+/// How the latest timing of a part compares with its baseline
+#[derive(Clone, Debug, PartialEq)]
+pub struct Comparison {
+    pub latest: Record,
+    /// `None` when the part was recorded only once
+    pub baseline: Option<Record>,
+    /// Change of the median time in percent
+    pub change: Option<f64>,
+    pub trend: Trend,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Trend {
+    Regressed,
+    Improved,
+    Unchanged,
+    /// Nothing to compare with
+    New,
+}
+
+/// Compares the latest timing of every part with its baseline
+///
+/// The baseline is the latest earlier timing of a commit starting with
+/// `baseline`, or the timing recorded just before the latest one. Changes
+/// within `threshold` percent count as noise.
+pub fn compare(history: &History, baseline: Option<&str>, threshold: f64) -> Vec<Comparison> {
+    let mut by_part: BTreeMap<(u8, Part), Vec<&Record>> = BTreeMap::new();
+    for record in history.records() {
+        by_part
+            .entry((record.day, record.part))
+            .or_default()
+            .push(record);
+    }
+
+    by_part
+        .into_values()
+        .filter_map(|mut records| {
+            let latest = records.pop()?;
+            let baseline = match baseline {
+                Some(commit) => records
+                    .into_iter()
+                    .rev()
+                    .find(|record| record.commit.starts_with(commit)),
+                None => records.pop(),
+            };
+            let change = baseline.map(|baseline| {
+                let (before, after) = (baseline.median.as_secs_f64(), latest.median.as_secs_f64());
+                if before == 0.0 {
+                    0.0
+                } else {
+                    (after - before) / before * 100.0
+                }
+            });
+            let trend = match change {
+                None => Trend::New,
+                Some(change) if change > threshold => Trend::Regressed,
+                Some(change) if change < -threshold => Trend::Improved,
+                Some(_) => Trend::Unchanged,
+            };
+            Some(Comparison {
+                latest: latest.clone(),
+                baseline: baseline.cloned(),
+                change,
+                trend,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[global_allocator]
+    static ALLOCATOR: PeakAllocator = PeakAllocator;
+
+    fn record(commit: &str, day: u8, part: Part, millis: u64) -> Record {
+        Record {
+            timestamp: 1_734_393_600,
+            commit: commit.to_string(),
+            day,
+            part,
+            median: Duration::from_millis(millis),
+            peak_memory: Some(2048),
+        }
+    }
+
+    #[test]
+    fn keeps_records_in_the_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("perf_history.csv");
+        let first = record("947febb", 12, Part::First, 71);
+        let second = Record {
+            peak_memory: None,
+            ..record("f60e0a7-dirty", 12, Part::Second, 35)
+        };
+        History::append(&path, std::slice::from_ref(&first)).unwrap();
+        History::append(&path, std::slice::from_ref(&second)).unwrap();
+
+        let text = fs::read_to_string(&path).unwrap();
+        assert_eq!(
+            "timestamp,commit,day,part,median_ns,peak_bytes\n\
+             1734393600,947febb,12,1,71000000,2048\n\
+             1734393600,f60e0a7-dirty,12,2,35000000,\n",
+            text
+        );
+        assert_eq!(vec![first, second], History::load(&path).unwrap().records);
+        assert!(History::parse("1,abc,12,3,5,").is_err());
+    }
+
+    #[test]
+    fn flags_regressions() {
+        let history = History {
+            records: vec![
+                record("aaa", 12, Part::First, 100),
+                record("aaa", 12, Part::Second, 100),
+                record("bbb", 12, Part::First, 120),
+                record("bbb", 12, Part::Second, 105),
+                record("ccc", 12, Part::First, 50),
+                record("ccc", 15, Part::First, 10),
+            ],
+        };
+
+        let trends = |comparisons: Vec<Comparison>| -> Vec<(u8, Part, Trend)> {
+            comparisons
+                .iter()
+                .map(|c| (c.latest.day, c.latest.part, c.trend))
+                .collect()
+        };
+        assert_eq!(
+            vec![
+                (12, Part::First, Trend::Improved),
+                (12, Part::Second, Trend::Unchanged),
+                (15, Part::First, Trend::New),
+            ],
+            trends(compare(&history, None, 10.0))
+        );
+        let against_aaa = compare(&history, Some("aa"), 10.0);
+        assert_eq!(Some(-50.0), against_aaa[0].change);
+        assert_eq!((12, Part::Second, Trend::Unchanged), trends(against_aaa)[1]);
+        assert_eq!(
+            (12, Part::Second, Trend::Regressed),
+            trends(compare(&history, None, 2.0))[1]
+        );
+    }
+
+    #[test]
+    fn measures_peak_memory() {
+        let (_, peak) = measure_peak(|| vec![0u8; 1 << 20].len());
+        assert!(peak.unwrap() >= 1 << 20);
+    }
+
+    #[test]
+    fn takes_the_median() {
+        let mut durations = [5, 1, 3].map(Duration::from_millis);
+        assert_eq!(Duration::from_millis(3), median(&mut durations));
+        let mut durations = [4, 1, 3, 2].map(Duration::from_millis);
+        assert_eq!(Duration::from_micros(2500), median(&mut durations));
+    }
+}
@@ -0,0 +1,370 @@
+use std::fmt;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+/// Number type of the coordinates of points
+pub trait Coordinate:
+    Copy + Ord + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self>
+{
+    /// The absolute difference, which does not overflow for unsigned numbers
+    fn abs_diff(self, other: Self) -> Self;
+}
+
+macro_rules! coordinate {
+    ($($number:ty),*) => {
+        $(
+            impl Coordinate for $number {
+                fn abs_diff(self, other: $number) -> $number {
+                    if self < other { other - self } else { self - other }
+                }
+            }
+        )*
+    };
+}
+
+coordinate!(
+    i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize
+);
+
+/// A position or a vector in the plane
+///
+/// `y` grows downwards like the rows of a [`Grid`](crate::grid::Grid), so
+/// [`Direction::Up`] decreases it.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point2<T> {
+    pub x: T,
+    pub y: T,
+}
+
+impl<T> Point2<T> {
+    pub const fn new(x: T, y: T) -> Point2<T> {
+        Point2 { x, y }
+    }
+}
+
+impl<T: Coordinate> Point2<T> {
+    /// Steps along the axes to get from one point to the other
+    pub fn manhattan(self, other: Point2<T>) -> T {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+
+    /// Steps in any of the eight directions to get from one point to the other
+    pub fn chebyshev(self, other: Point2<T>) -> T {
+        self.x.abs_diff(other.x).max(self.y.abs_diff(other.y))
+    }
+
+    /// Square of the straight line distance, which keeps the order of the distances
+    pub fn euclidean_squared(self, other: Point2<T>) -> T {
+        let (dx, dy) = (self.x.abs_diff(other.x), self.y.abs_diff(other.y));
+        dx * dx + dy * dy
+    }
+}
+
+impl<T: Add<Output = T>> Add for Point2<T> {
+    type Output = Point2<T>;
+
+    fn add(self, other: Point2<T>) -> Point2<T> {
+        Point2::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Point2<T> {
+    type Output = Point2<T>;
+
+    fn sub(self, other: Point2<T>) -> Point2<T> {
+        Point2::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl<T: Copy + Add<Output = T>> AddAssign for Point2<T> {
+    fn add_assign(&mut self, other: Point2<T>) {
+        *self = *self + other;
+    }
+}
+
+impl<T: Copy + Sub<Output = T>> SubAssign for Point2<T> {
+    fn sub_assign(&mut self, other: Point2<T>) {
+        *self = *self - other;
+    }
+}
+
+/// Scales the vector
+impl<T: Copy + Mul<Output = T>> Mul<T> for Point2<T> {
+    type Output = Point2<T>;
+
+    fn mul(self, factor: T) -> Point2<T> {
+        Point2::new(self.x * factor, self.y * factor)
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Point2<T> {
+    type Output = Point2<T>;
+
+    fn neg(self) -> Point2<T> {
+        Point2::new(-self.x, -self.y)
+    }
+}
+
+impl<T> From<(T, T)> for Point2<T> {
+    fn from((x, y): (T, T)) -> Point2<T> {
+        Point2::new(x, y)
+    }
+}
+
+/// A step in the direction, e.g. for [`Grid::offset`](crate::grid::Grid::offset)
+impl<T: From<i8>> From<Direction> for Point2<T> {
+    fn from(direction: Direction) -> Point2<T> {
+        direction.vector()
+    }
+}
+
+impl<T> From<Point2<T>> for (T, T) {
+    fn from(point: Point2<T>) -> (T, T) {
+        (point.x, point.y)
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Point2<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{},{}", self.x, self.y)
+    }
+}
+
+/// A position or a vector in space
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+impl<T> Point3<T> {
+    pub const fn new(x: T, y: T, z: T) -> Point3<T> {
+        Point3 { x, y, z }
+    }
+}
+
+impl<T: Coordinate> Point3<T> {
+    /// Steps along the axes to get from one point to the other
+    pub fn manhattan(self, other: Point3<T>) -> T {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y) + self.z.abs_diff(other.z)
+    }
+
+    /// Steps in any of the 26 directions to get from one point to the other
+    pub fn chebyshev(self, other: Point3<T>) -> T {
+        self.x
+            .abs_diff(other.x)
+            .max(self.y.abs_diff(other.y))
+            .max(self.z.abs_diff(other.z))
+    }
+
+    /// Square of the straight line distance, which keeps the order of the distances
+    pub fn euclidean_squared(self, other: Point3<T>) -> T {
+        let (dx, dy, dz) = (
+            self.x.abs_diff(other.x),
+            self.y.abs_diff(other.y),
+            self.z.abs_diff(other.z),
+        );
+        dx * dx + dy * dy + dz * dz
+    }
+}
+
+impl<T: Add<Output = T>> Add for Point3<T> {
+    type Output = Point3<T>;
+
+    fn add(self, other: Point3<T>) -> Point3<T> {
+        Point3::new(self.x + other.x, self.y + other.y, self.z + other.z)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Point3<T> {
+    type Output = Point3<T>;
+
+    fn sub(self, other: Point3<T>) -> Point3<T> {
+        Point3::new(self.x - other.x, self.y - other.y, self.z - other.z)
+    }
+}
+
+impl<T: Copy + Add<Output = T>> AddAssign for Point3<T> {
+    fn add_assign(&mut self, other: Point3<T>) {
+        *self = *self + other;
+    }
+}
+
+impl<T: Copy + Sub<Output = T>> SubAssign for Point3<T> {
+    fn sub_assign(&mut self, other: Point3<T>) {
+        *self = *self - other;
+    }
+}
+
+/// Scales the vector
+impl<T: Copy + Mul<Output = T>> Mul<T> for Point3<T> {
+    type Output = Point3<T>;
+
+    fn mul(self, factor: T) -> Point3<T> {
+        Point3::new(self.x * factor, self.y * factor, self.z * factor)
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Point3<T> {
+    type Output = Point3<T>;
+
+    fn neg(self) -> Point3<T> {
+        Point3::new(-self.x, -self.y, -self.z)
+    }
+}
+
+impl<T> From<(T, T, T)> for Point3<T> {
+    fn from((x, y, z): (T, T, T)) -> Point3<T> {
+        Point3::new(x, y, z)
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Point3<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{},{},{}", self.x, self.y, self.z)
+    }
+}
+
+/// One of the eight directions in the plane, in clockwise order starting up
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl Direction {
+    /// Up, right, down and left
+    pub const ORTHOGONAL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    /// All directions, clockwise starting up
+    pub const ALL: [Direction; 8] = [
+        Direction::Up,
+        Direction::UpRight,
+        Direction::Right,
+        Direction::DownRight,
+        Direction::Down,
+        Direction::DownLeft,
+        Direction::Left,
+        Direction::UpLeft,
+    ];
+
+    /// Reads `U`, `R`, `D` and `L`, the compass points `N`, `E`, `S` and `W`
+    /// and the arrows `^`, `>`, `v` and `<`
+    pub fn from_char(c: char) -> Option<Direction> {
+        match c {
+            'U' | 'N' | '^' => Some(Direction::Up),
+            'R' | 'E' | '>' => Some(Direction::Right),
+            'D' | 'S' | 'v' => Some(Direction::Down),
+            'L' | 'W' | '<' => Some(Direction::Left),
+            _ => None,
+        }
+    }
+
+    /// The direction a quarter turn counterclockwise
+    pub fn turn_left(self) -> Direction {
+        self.rotate(6)
+    }
+
+    /// The direction a quarter turn clockwise
+    pub fn turn_right(self) -> Direction {
+        self.rotate(2)
+    }
+
+    pub fn opposite(self) -> Direction {
+        self.rotate(4)
+    }
+
+    /// Whether the direction is along one of the axes
+    pub fn is_orthogonal(self) -> bool {
+        (self as usize).is_multiple_of(2)
+    }
+
+    /// Change of `x` and `y` of a step in the direction
+    pub fn offset(self) -> (isize, isize) {
+        match self {
+            Direction::Up => (0, -1),
+            Direction::UpRight => (1, -1),
+            Direction::Right => (1, 0),
+            Direction::DownRight => (1, 1),
+            Direction::Down => (0, 1),
+            Direction::DownLeft => (-1, 1),
+            Direction::Left => (-1, 0),
+            Direction::UpLeft => (-1, -1),
+        }
+    }
+
+    /// A step in the direction as a vector
+    pub fn vector<T: From<i8>>(self) -> Point2<T> {
+        let (dx, dy) = self.offset();
+        Point2::new(T::from(dx as i8), T::from(dy as i8))
+    }
+
+    /// The direction `eighths` eighths of a turn clockwise
+    fn rotate(self, eighths: usize) -> Direction {
+        Direction::ALL[(self as usize + eighths) % 8]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn calculates_with_points() {
+        let a = Point2::new(1, 2);
+        let b = Point2::new(4, -2);
+        assert_eq!(Point2::new(5, 0), a + b);
+        assert_eq!(Point2::new(-3, 4), a - b);
+        assert_eq!(Point2::new(3, 6), a * 3);
+        assert_eq!(Point2::new(-1, -2), -a);
+        assert_eq!(7, a.manhattan(b));
+        assert_eq!(4, a.chebyshev(b));
+        assert_eq!(25, a.euclidean_squared(b));
+        assert_eq!(2, Point2::new(3_usize, 0).manhattan(Point2::new(2, 1)));
+
+        let mut c = Point3::new(1_i64, 2, 3);
+        c += Point3::new(1, 1, 1);
+        assert_eq!(Point3::new(2, 3, 4), c);
+        assert_eq!(7, c.manhattan(Point3::new(0, 0, 2)));
+        assert_eq!(3, c.chebyshev(Point3::new(0, 0, 2)));
+        assert_eq!(17, c.euclidean_squared(Point3::new(0, 0, 2)));
+        assert_eq!("2,3,4", c.to_string());
+    }
+
+    #[test]
+    fn turns_directions() {
+        assert_eq!(Direction::Left, Direction::Up.turn_left());
+        assert_eq!(Direction::Up, Direction::Left.turn_right());
+        assert_eq!(Direction::DownRight, Direction::UpRight.turn_right());
+        assert_eq!(Direction::DownLeft, Direction::UpRight.opposite());
+        assert!(Direction::ALL.iter().all(|&d| d.opposite().opposite() == d));
+        assert!(
+            Direction::ALL
+                .iter()
+                .all(|&d| d.vector::<i32>() == -d.opposite().vector())
+        );
+        assert_eq!(
+            Direction::ORTHOGONAL.to_vec(),
+            Direction::ALL
+                .iter()
+                .copied()
+                .filter(|d| d.is_orthogonal())
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(Point2::new(0, -1), Direction::Up.vector::<i64>());
+        assert_eq!(Some(Direction::Left), Direction::from_char('<'));
+        assert_eq!(Some(Direction::Down), Direction::from_char('D'));
+        assert_eq!(None, Direction::from_char('x'));
+    }
+}
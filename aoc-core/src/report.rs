@@ -0,0 +1,381 @@
+use std::time::Duration;
+
+use crate::answer::Answer;
+use crate::answers::{Check, Verdict};
+use crate::perf::{Comparison, Trend};
+use crate::runner::{DayReport, Failure, Outcome};
+use crate::solution::Part;
+
+const TIMING_HEADER: [&str; 8] = [
+    "Day",
+    "Title",
+    "Part 1",
+    "Part 2",
+    "Parse",
+    "Part 1 time",
+    "Part 2 time",
+    "Total",
+];
+
+/// Columns of the timing table holding durations, aligned to the right
+const DURATION_COLUMNS: [usize; 4] = [4, 5, 6, 7];
+
+const VERIFY_HEADER: [&str; 6] = ["Day", "Title", "Part", "Result", "Expected", "Actual"];
+
+const PERF_HEADER: [&str; 9] = [
+    "Day",
+    "Part",
+    "Baseline",
+    "Time",
+    "Latest",
+    "Time",
+    "Change",
+    "Peak memory",
+    "Result",
+];
+
+/// Columns of the performance table holding numbers, aligned to the right
+const PERF_NUMBER_COLUMNS: [usize; 4] = [3, 5, 6, 7];
+
+/// Table of answers and timings with a row per day and a row with the sums
+pub fn table(reports: &[DayReport]) -> String {
+    let rows: Vec<Vec<String>> = reports.iter().map(row).collect();
+
+    let mut parse_total = Duration::ZERO;
+    let mut part_totals = [Duration::ZERO; 2];
+    for report in reports {
+        if let Outcome::Solved(run) = &report.outcome {
+            parse_total += run.parse_duration;
+            for part_run in &run.parts {
+                part_totals[usize::from(part_run.part.number()) - 1] += part_run.duration;
+            }
+        }
+    }
+    let footer = vec![
+        String::from("Total"),
+        String::new(),
+        String::new(),
+        String::new(),
+        format_duration(parse_total),
+        format_duration(part_totals[0]),
+        format_duration(part_totals[1]),
+        format_duration(reports.iter().map(DayReport::total_duration).sum()),
+    ];
+
+    render(&TIMING_HEADER, rows, Some(footer), &DURATION_COLUMNS)
+}
+
+/// Table with a row per checked part and a summary of the verdicts
+pub fn verify_table(checks: &[Check]) -> String {
+    let rows = checks
+        .iter()
+        .map(|check| {
+            vec![
+                check.day.to_string(),
+                check.title.to_string(),
+                check.part.to_string(),
+                verdict_cell(check.verdict).to_string(),
+                check.expected.as_ref().map_or(String::new(), answer_cell),
+                check.actual.clone(),
+            ]
+        })
+        .collect();
+    let count = |verdict| checks.iter().filter(|c| c.verdict == verdict).count();
+    let failed = checks.iter().filter(|c| c.verdict.is_failure()).count();
+    let footer = vec![
+        String::from("Total"),
+        format!(
+            "{} passed, {failed} failed, {} not implemented, {} missing",
+            count(Verdict::Pass),
+            count(Verdict::NotImplemented),
+            count(Verdict::Missing)
+        ),
+    ];
+
+    render(&VERIFY_HEADER, rows, Some(footer), &[])
+}
+
+/// Table comparing the latest timing of every part with its baseline and a summary of the trends
+pub fn perf_table(comparisons: &[Comparison]) -> String {
+    let rows = comparisons
+        .iter()
+        .map(|comparison| {
+            let latest = &comparison.latest;
+            let baseline = comparison.baseline.as_ref();
+            vec![
+                latest.day.to_string(),
+                latest.part.to_string(),
+                baseline.map_or(String::new(), |baseline| baseline.commit.clone()),
+                baseline.map_or(String::new(), |baseline| format_duration(baseline.median)),
+                latest.commit.clone(),
+                format_duration(latest.median),
+                comparison
+                    .change
+                    .map_or(String::new(), |change| format!("{change:+.1}%")),
+                latest.peak_memory.map_or(String::new(), format_bytes),
+                trend_cell(comparison.trend).to_string(),
+            ]
+        })
+        .collect();
+    let count = |trend| comparisons.iter().filter(|c| c.trend == trend).count();
+    let footer = vec![
+        String::from("Total"),
+        format!(
+            "{} regressed, {} improved, {} unchanged, {} new",
+            count(Trend::Regressed),
+            count(Trend::Improved),
+            count(Trend::Unchanged),
+            count(Trend::New)
+        ),
+    ];
+
+    render(&PERF_HEADER, rows, Some(footer), &PERF_NUMBER_COLUMNS)
+}
+
+fn trend_cell(trend: Trend) -> &'static str {
+    match trend {
+        Trend::Regressed => "REGRESSED",
+        Trend::Improved => "improved",
+        Trend::Unchanged => "unchanged",
+        Trend::New => "new",
+    }
+}
+
+fn verdict_cell(verdict: Verdict) -> &'static str {
+    match verdict {
+        Verdict::Pass => "pass",
+        Verdict::Wrong => "WRONG",
+        Verdict::ParseFailed => "PARSE ERROR",
+        Verdict::Panicked => "PANICKED",
+        Verdict::TimedOut => "TIMED OUT",
+        Verdict::NotImplemented => "not implemented",
+        Verdict::Missing => "missing",
+    }
+}
+
+/// The kind of failure, the panic message is left out to keep the table narrow
+fn failure_cell(failure: &Failure) -> String {
+    match failure {
+        Failure::Panicked(_) => String::from("panicked"),
+        failure => failure.to_string(),
+    }
+}
+
+/// Lines up the cells of the rows under the header, the footer is set apart by a separator
+fn render(
+    header: &[&str],
+    rows: Vec<Vec<String>>,
+    footer: Option<Vec<String>>,
+    right_aligned: &[usize],
+) -> String {
+    let mut lines: Vec<Vec<String>> = vec![header.iter().map(|title| title.to_string()).collect()];
+    lines.push(Vec::new());
+    lines.extend(rows);
+    if let Some(footer) = footer {
+        lines.push(Vec::new());
+        lines.push(footer);
+    }
+
+    let widths: Vec<usize> = (0..header.len())
+        .map(|column| {
+            lines
+                .iter()
+                .filter(|line| line.len() == header.len())
+                .map(|line| line[column].chars().count())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+    let separator: Vec<String> = widths.iter().map(|&width| "-".repeat(width)).collect();
+
+    lines
+        .iter()
+        .map(|line| match line.len() {
+            0 => format_row(&separator, &widths, right_aligned),
+            _ => format_row(line, &widths, right_aligned),
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+fn row(report: &DayReport) -> Vec<String> {
+    let mut row = vec![report.day.to_string(), report.title.to_string()];
+    match &report.outcome {
+        Outcome::Solved(run) => {
+            let part_run = |part| run.parts.iter().find(|p| p.part == part);
+            row.extend(Part::BOTH.map(|part| match part_run(part) {
+                Some(part_run) => answer_cell(&part_run.answer),
+                None => report.failure(part).map_or(String::new(), failure_cell),
+            }));
+            if run.parts.is_empty() {
+                row.extend(["", "", "", ""].map(String::from));
+                return row;
+            }
+            row.push(format_duration(run.parse_duration));
+            row.extend(
+                Part::BOTH.map(|part| {
+                    part_run(part).map_or(String::new(), |p| format_duration(p.duration))
+                }),
+            );
+            row.push(format_duration(report.total_duration()));
+        }
+        Outcome::ParseFailed(_) => {
+            row.extend(["parse error", "parse error"].map(String::from));
+            row.extend(["", "", "", ""].map(String::from));
+        }
+        Outcome::ParseAborted(failure) => {
+            let cell = format!("parsing {}", failure_cell(failure));
+            row.extend([cell.clone(), cell]);
+            row.extend(["", "", "", ""].map(String::from));
+        }
+        Outcome::MissingInput => {
+            row.extend(["missing input", "missing input"].map(String::from));
+            row.extend(["", "", "", ""].map(String::from));
+        }
+    }
+    row
+}
+
+/// Answers that do not fit on a single line are summarized
+fn answer_cell(answer: &Answer) -> String {
+    match answer {
+        Answer::Grid(grid) => format!("<{}x{} grid>", grid.width(), grid.height()),
+        answer => answer.to_string().replace('\n', " "),
+    }
+}
+
+fn format_duration(duration: Duration) -> String {
+    format!("{duration:.1?}")
+}
+
+/// Size in bytes with a binary unit, e.g. `1.5MiB`
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+    if bytes < 1024 {
+        return format!("{bytes}B");
+    }
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    format!("{size:.1}{}", UNITS[unit])
+}
+
+fn format_row(row: &[String], widths: &[usize], right_aligned: &[usize]) -> String {
+    row.iter()
+        .zip(widths)
+        .enumerate()
+        .map(|(column, (cell, &width))| {
+            if right_aligned.contains(&column) {
+                format!("{cell:>width$}")
+            } else {
+                format!("{cell:<width$}")
+            }
+        })
+        .collect::<Vec<String>>()
+        .join("  ")
+        .trim_end()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::perf::Record;
+    use crate::solution::{PartRun, Run};
+
+    #[test]
+    fn formats_table() {
+        let reports = vec![
+            DayReport {
+                day: 1,
+                title: "First",
+                outcome: Outcome::Solved(Run {
+                    parse_duration: Duration::from_millis(2),
+                    parts: vec![
+                        PartRun {
+                            part: Part::First,
+                            answer: Answer::from(42),
+                            duration: Duration::from_micros(1500),
+                        },
+                        PartRun {
+                            part: Part::Second,
+                            answer: Answer::from("ABC"),
+                            duration: Duration::from_secs(1),
+                        },
+                    ],
+                }),
+                failures: Vec::new(),
+            },
+            DayReport {
+                day: 3,
+                title: "Third",
+                outcome: Outcome::Solved(Run {
+                    parse_duration: Duration::from_millis(1),
+                    parts: vec![PartRun {
+                        part: Part::Second,
+                        answer: Answer::from(7),
+                        duration: Duration::from_millis(3),
+                    }],
+                }),
+                failures: vec![(Part::First, Failure::Panicked(String::from("overflow")))],
+            },
+            DayReport {
+                day: 12,
+                title: "Second",
+                outcome: Outcome::MissingInput,
+                failures: Vec::new(),
+            },
+        ];
+        let expected = "\
+Day    Title   Part 1         Part 2         Parse  Part 1 time  Part 2 time  Total
+-----  ------  -------------  -------------  -----  -----------  -----------  -----
+1      First   42             ABC            2.0ms        1.5ms         1.0s   1.0s
+3      Third   panicked       7              1.0ms                     3.0ms  4.0ms
+12     Second  missing input  missing input
+-----  ------  -------------  -------------  -----  -----------  -----------  -----
+Total                                        3.0ms        1.5ms         1.0s   1.0s";
+        assert_eq!(expected, table(&reports));
+    }
+
+    #[test]
+    fn formats_perf_table() {
+        let record = |commit: &str, millis, peak_memory| Record {
+            timestamp: 0,
+            commit: String::from(commit),
+            day: 12,
+            part: Part::First,
+            median: Duration::from_millis(millis),
+            peak_memory,
+        };
+        let comparisons = vec![
+            Comparison {
+                latest: record("bbb", 90, Some(3 << 20)),
+                baseline: Some(record("aaa", 60, Some(1 << 20))),
+                change: Some(50.0),
+                trend: Trend::Regressed,
+            },
+            Comparison {
+                latest: Record {
+                    day: 15,
+                    ..record("bbb", 2, None)
+                },
+                baseline: None,
+                change: None,
+                trend: Trend::New,
+            },
+        ];
+        let expected = "\
+Day  Part  Baseline    Time  Latest    Time  Change  Peak memory  Result
+---  ----  --------  ------  ------  ------  ------  -----------  ---------
+12   1     aaa       60.0ms  bbb     90.0ms  +50.0%       3.0MiB  REGRESSED
+15   1                       bbb      2.0ms                       new
+---  ----  --------  ------  ------  ------  ------  -----------  ---------
+Total  1 regressed, 0 improved, 0 unchanged, 1 new";
+        assert_eq!(expected, perf_table(&comparisons));
+        assert_eq!("512B", format_bytes(512));
+        assert_eq!("1.5KiB", format_bytes(1536));
+    }
+}
@@ -0,0 +1,570 @@
+use std::any::Any;
+use std::fmt;
+use std::ops::RangeInclusive;
+use std::panic::{self, AssertUnwindSafe};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
+use std::time::Duration;
+
+use crate::input_utils::InputDirs;
+use crate::params::Params;
+use crate::parse::ParseError;
+use crate::solution::{Part, Puzzle, Puzzles, Run};
+
+/// Stack size of the threads solving days in parallel or with a timeout, as
+/// large as the one of the main thread on Linux so recursive solutions behave the same
+const STACK_SIZE: usize = 8 * 1024 * 1024;
+
+/// Selection of days to solve in one go
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Days {
+    All,
+    Year(u16),
+    Range(u8, u8),
+}
+
+impl Days {
+    /// The selected days of the given year, `None` if another year was asked for
+    pub fn resolve(self, year: u16) -> Option<RangeInclusive<u8>> {
+        match self {
+            Days::All => Some(1..=25),
+            Days::Year(selected) if selected == year => Some(1..=25),
+            Days::Year(_) => None,
+            Days::Range(from, to) => Some(from..=to),
+        }
+    }
+}
+
+impl FromStr for Days {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Days, String> {
+        let day = |s: &str| match s.parse() {
+            Ok(day @ 1..=25) => Ok(day),
+            _ => Err(format!("`{s}` is not a day between 1 and 25")),
+        };
+        if s == "all" {
+            Ok(Days::All)
+        } else if let Some((from, to)) = s.split_once('-') {
+            let (from, to) = (day(from)?, day(to)?);
+            if from > to {
+                return Err(format!("Day range `{s}` is empty"));
+            }
+            Ok(Days::Range(from, to))
+        } else if s.len() == 4 {
+            s.parse()
+                .map(Days::Year)
+                .map_err(|_| format!("`{s}` is not a year"))
+        } else {
+            day(s).map(|day| Days::Range(day, day))
+        }
+    }
+}
+
+impl fmt::Display for Days {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Days::All => write!(f, "all"),
+            Days::Year(year) => write!(f, "{year}"),
+            Days::Range(from, to) if from == to => write!(f, "{from}"),
+            Days::Range(from, to) => write!(f, "{from}-{to}"),
+        }
+    }
+}
+
+/// What happened when solving a single day
+#[derive(Debug)]
+pub enum Outcome {
+    /// The input was parsed, the run holds the parts that were solved
+    Solved(Run),
+    ParseFailed(ParseError),
+    /// Parsing panicked or was abandoned after the timeout, neither part was solved
+    ParseAborted(Failure),
+    MissingInput,
+}
+
+/// Why a part was not solved
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Failure {
+    /// The part panicked with this message
+    Panicked(String),
+    /// The part is still a `todo!()` or `unimplemented!()`
+    NotImplemented,
+    /// The part was not solved within this time and was abandoned
+    TimedOut(Duration),
+}
+
+impl Failure {
+    fn from_panic(payload: Box<dyn Any + Send>) -> Failure {
+        let message = payload
+            .downcast_ref::<&str>()
+            .map(|message| message.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_default();
+        if message.starts_with("not yet implemented") || message.starts_with("not implemented") {
+            Failure::NotImplemented
+        } else {
+            Failure::Panicked(message)
+        }
+    }
+}
+
+impl fmt::Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Failure::Panicked(message) if message.is_empty() => write!(f, "panicked"),
+            Failure::Panicked(message) => write!(f, "panicked: {message}"),
+            Failure::NotImplemented => write!(f, "not implemented"),
+            Failure::TimedOut(timeout) => write!(f, "timed out after {timeout:?}"),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct DayReport {
+    pub day: u8,
+    pub title: &'static str,
+    pub outcome: Outcome,
+    /// The parts of a parsed input that were not solved
+    pub failures: Vec<(Part, Failure)>,
+}
+
+impl DayReport {
+    pub fn failure(&self, part: Part) -> Option<&Failure> {
+        self.failures
+            .iter()
+            .find(|(failed, _)| *failed == part)
+            .map(|(_, failure)| failure)
+    }
+
+    /// Time spent parsing and solving, zero if nothing was solved
+    pub fn total_duration(&self) -> Duration {
+        match &self.outcome {
+            Outcome::Solved(run) => {
+                run.parse_duration + run.parts.iter().map(|p| p.duration).sum::<Duration>()
+            }
+            _ => Duration::ZERO,
+        }
+    }
+}
+
+/// Solves both parts of every implemented day within `days`, in order of the days
+///
+/// With more than one job the days are solved on that many threads at once,
+/// the reports still come in order of the days. Solving in parallel makes the
+/// days compete for the processor, see [`retime`] for accurate timings.
+///
+/// The input of a day is parsed once, then every part is solved on its own, so
+/// a part that panics or takes longer than the `timeout` is reported as a
+/// failure without keeping the others from being solved.
+pub fn run_days(
+    puzzles: &Puzzles,
+    days: RangeInclusive<u8>,
+    dirs: &InputDirs,
+    jobs: usize,
+    timeout: Option<Duration>,
+) -> Vec<DayReport> {
+    let selected: Vec<&Puzzle> = puzzles
+        .iter()
+        .filter(|puzzle| days.contains(&puzzle.day))
+        .collect();
+    if jobs <= 1 || selected.len() <= 1 {
+        return selected
+            .into_iter()
+            .map(|puzzle| run_day(puzzle, dirs, timeout))
+            .collect();
+    }
+
+    let next = AtomicUsize::new(0);
+    let mut reports: Vec<Option<DayReport>> = selected.iter().map(|_| None).collect();
+    thread::scope(|scope| {
+        let workers: Vec<_> = (0..jobs.min(selected.len()))
+            .map(|_| {
+                thread::Builder::new()
+                    .stack_size(STACK_SIZE)
+                    .spawn_scoped(scope, || {
+                        let mut done = Vec::new();
+                        loop {
+                            let index = next.fetch_add(1, Ordering::Relaxed);
+                            let Some(puzzle) = selected.get(index) else {
+                                return done;
+                            };
+                            done.push((index, run_day(puzzle, dirs, timeout)));
+                        }
+                    })
+                    .expect("Could not start a thread")
+            })
+            .collect();
+        for worker in workers {
+            let done = worker
+                .join()
+                .unwrap_or_else(|panic| panic::resume_unwind(panic));
+            for (index, report) in done {
+                reports[index] = Some(report);
+            }
+        }
+    });
+    reports.into_iter().flatten().collect()
+}
+
+/// Solves the solved days once more, one after the other, and keeps those timings
+pub fn retime(
+    puzzles: &Puzzles,
+    reports: &mut [DayReport],
+    dirs: &InputDirs,
+    timeout: Option<Duration>,
+) {
+    for report in reports {
+        if let (Outcome::Solved(_), Some(puzzle)) = (&report.outcome, puzzles.get(report.day)) {
+            *report = run_day(puzzle, dirs, timeout);
+        }
+    }
+}
+
+fn run_day(puzzle: &Puzzle, dirs: &InputDirs, timeout: Option<Duration>) -> DayReport {
+    match dirs.read_input(puzzle.day.into()) {
+        Ok(input) => solve_input(
+            puzzle,
+            &input,
+            &Part::BOTH,
+            &Params::defaults(puzzle.params),
+            timeout,
+        ),
+        Err(_) => DayReport {
+            day: puzzle.day,
+            title: puzzle.title,
+            outcome: Outcome::MissingInput,
+            failures: Vec::new(),
+        },
+    }
+}
+
+/// Parses the input once and solves the given parts from it, parsing and
+/// every part guarded on their own, see [`guarded`]
+pub fn solve_input(
+    puzzle: &Puzzle,
+    input: &str,
+    parts: &[Part],
+    params: &Params,
+    timeout: Option<Duration>,
+) -> DayReport {
+    let mut report = DayReport {
+        day: puzzle.day,
+        title: puzzle.title,
+        outcome: Outcome::MissingInput,
+        failures: Vec::new(),
+    };
+    let (puzzle, input, params) = (*puzzle, input.to_string(), params.clone());
+    report.outcome = match guarded(move || puzzle.prepare(&input, &params), timeout) {
+        Ok(Ok(prepared)) => {
+            let mut solved = Vec::new();
+            for &part in parts {
+                match guarded(prepared.task(part), timeout) {
+                    Ok(part_run) => solved.push(part_run),
+                    Err(failure) => report.failures.push((part, failure)),
+                }
+            }
+            Outcome::Solved(Run {
+                parse_duration: prepared.parse_duration,
+                parts: solved,
+            })
+        }
+        Ok(Err(error)) => Outcome::ParseFailed(error),
+        Err(failure) => Outcome::ParseAborted(failure),
+    };
+    report
+}
+
+/// Runs a step of solving, catching a panic
+///
+/// With a timeout the step runs on a thread of its own that is abandoned when
+/// it takes longer, it keeps running in the background until it finishes or
+/// the process exits.
+pub fn guarded<T: Send + 'static>(
+    step: impl FnOnce() -> T + Send + 'static,
+    timeout: Option<Duration>,
+) -> Result<T, Failure> {
+    let Some(timeout) = timeout else {
+        return panic::catch_unwind(AssertUnwindSafe(step)).map_err(Failure::from_panic);
+    };
+
+    let (sender, receiver) = mpsc::channel();
+    thread::Builder::new()
+        .stack_size(STACK_SIZE)
+        .spawn(move || {
+            let result = panic::catch_unwind(AssertUnwindSafe(step));
+            // Nobody is waiting any more after a timeout
+            let _ = sender.send(result);
+        })
+        .expect("Could not start a thread");
+    match receiver.recv_timeout(timeout) {
+        Ok(result) => result.map_err(Failure::from_panic),
+        Err(RecvTimeoutError::Timeout) => Err(Failure::TimedOut(timeout)),
+        Err(RecvTimeoutError::Disconnected) => Err(Failure::Panicked(String::new())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+    use crate::answer::Answer;
+    use crate::parse;
+    use crate::solution::Solution;
+
+    /// Takes longer the earlier the day, so parallel runs finish out of order
+    struct Sleepy<const DAY: u8>;
+
+    impl<const DAY: u8> Solution for Sleepy<DAY> {
+        const DAY: u8 = DAY;
+        const TITLE: &'static str = "Sleepy";
+        type Input = u64;
+
+        fn parse(input: &str) -> Result<u64, ParseError> {
+            parse::number(input, input.trim())
+        }
+
+        fn part1(input: &u64) -> Answer {
+            thread::sleep(Duration::from_millis(10 * (5 - u64::from(DAY))));
+            Answer::from(*input + u64::from(DAY))
+        }
+
+        fn part2(input: &u64) -> Answer {
+            Answer::from(*input * u64::from(DAY))
+        }
+    }
+
+    #[test]
+    fn reports_parallel_runs_in_order() {
+        let dir = tempfile::tempdir().unwrap();
+        for day in 1..=3 {
+            fs::write(dir.path().join(format!("day_0{day}_input.txt")), "10\n").unwrap();
+        }
+        let dirs = InputDirs::new(vec![dir.path().to_path_buf()]);
+        let puzzles = crate::puzzles![2022; Sleepy<1>, Sleepy<2>, Sleepy<3>, Sleepy<4>];
+
+        let answers = |reports: &[DayReport]| -> Vec<(u8, Vec<Answer>)> {
+            reports
+                .iter()
+                .map(|report| match &report.outcome {
+                    Outcome::Solved(run) => (
+                        report.day,
+                        run.parts.iter().map(|p| p.answer.clone()).collect(),
+                    ),
+                    _ => (report.day, Vec::new()),
+                })
+                .collect()
+        };
+        let serial = run_days(&puzzles, 1..=4, &dirs, 1, None);
+        let mut parallel = run_days(&puzzles, 1..=4, &dirs, 3, None);
+        assert_eq!(answers(&serial), answers(&parallel));
+        assert_eq!(
+            vec![
+                (1, vec![Answer::from(11), Answer::from(10)]),
+                (2, vec![Answer::from(12), Answer::from(20)]),
+                (3, vec![Answer::from(13), Answer::from(30)]),
+                (4, Vec::new()),
+            ],
+            answers(&parallel)
+        );
+
+        retime(&puzzles, &mut parallel, &dirs, None);
+        assert_eq!(answers(&serial), answers(&parallel));
+    }
+
+    /// Fails in a different way for every day
+    struct Failing<const DAY: u8>;
+
+    impl<const DAY: u8> Solution for Failing<DAY> {
+        const DAY: u8 = DAY;
+        const TITLE: &'static str = "Failing";
+        type Input = u64;
+
+        fn parse(input: &str) -> Result<u64, ParseError> {
+            parse::number(input, input.trim())
+        }
+
+        fn part1(input: &u64) -> Answer {
+            match DAY {
+                1 => todo!(),
+                2 => panic!("Day {DAY} is broken"),
+                _ => Answer::from(*input),
+            }
+        }
+
+        fn part2(input: &u64) -> Answer {
+            if DAY == 3 {
+                thread::sleep(Duration::from_secs(1));
+            }
+            Answer::from(*input * 2)
+        }
+    }
+
+    #[test]
+    fn isolates_failing_parts() {
+        let dir = tempfile::tempdir().unwrap();
+        for day in 1..=4 {
+            let input = if day == 4 { "ten\n" } else { "10\n" };
+            fs::write(dir.path().join(format!("day_0{day}_input.txt")), input).unwrap();
+        }
+        let dirs = InputDirs::new(vec![dir.path().to_path_buf()]);
+        let puzzles = crate::puzzles![2022; Failing<1>, Failing<2>, Failing<3>, Failing<4>];
+        let timeout = Duration::from_millis(100);
+
+        let reports = run_days(&puzzles, 1..=4, &dirs, 1, Some(timeout));
+        let solved = |report: &DayReport| match &report.outcome {
+            Outcome::Solved(run) => run
+                .parts
+                .iter()
+                .map(|p| (p.part, p.answer.clone()))
+                .collect(),
+            _ => Vec::new(),
+        };
+        assert_eq!(vec![(Part::Second, Answer::from(20))], solved(&reports[0]));
+        assert_eq!(
+            vec![(Part::First, Failure::NotImplemented)],
+            reports[0].failures
+        );
+        assert_eq!(
+            vec![(
+                Part::First,
+                Failure::Panicked(String::from("Day 2 is broken"))
+            )],
+            reports[1].failures
+        );
+        assert_eq!(vec![(Part::First, Answer::from(10))], solved(&reports[2]));
+        assert_eq!(
+            Some(&Failure::TimedOut(timeout)),
+            reports[2].failure(Part::Second)
+        );
+        assert!(matches!(reports[3].outcome, Outcome::ParseFailed(_)));
+        assert!(reports[3].failures.is_empty());
+
+        let reports = run_days(&puzzles, 2..=2, &dirs, 1, None);
+        assert_eq!(
+            Some(&Failure::Panicked(String::from("Day 2 is broken"))),
+            reports[0].failure(Part::First)
+        );
+        assert_eq!(
+            "panicked: Day 2 is broken",
+            reports[0].failures[0].1.to_string()
+        );
+    }
+
+    static PARSES: AtomicUsize = AtomicUsize::new(0);
+
+    struct Counted;
+
+    impl Solution for Counted {
+        const DAY: u8 = 1;
+        const TITLE: &'static str = "Counted";
+        type Input = u64;
+
+        fn parse(input: &str) -> Result<u64, ParseError> {
+            PARSES.fetch_add(1, Ordering::Relaxed);
+            parse::number(input, input.trim())
+        }
+
+        fn part1(input: &u64) -> Answer {
+            Answer::from(*input)
+        }
+
+        fn part2(_input: &u64) -> Answer {
+            panic!("Part 2 is broken")
+        }
+    }
+
+    #[test]
+    fn parses_each_input_once() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("day_01_input.txt"), "10\n").unwrap();
+        let dirs = InputDirs::new(vec![dir.path().to_path_buf()]);
+        let puzzles = crate::puzzles![2022; Counted];
+
+        for timeout in [None, Some(Duration::from_secs(10))] {
+            PARSES.store(0, Ordering::Relaxed);
+            let reports = run_days(&puzzles, 1..=1, &dirs, 1, timeout);
+            assert_eq!(1, PARSES.load(Ordering::Relaxed));
+            let Outcome::Solved(run) = &reports[0].outcome else {
+                panic!("Day 1 was not solved");
+            };
+            assert_eq!(1, run.parts.len());
+            assert!(run.parse_duration > Duration::ZERO);
+            assert!(reports[0].failure(Part::Second).is_some());
+        }
+    }
+
+    /// Panics or stalls while parsing some inputs
+    struct Stalling;
+
+    impl Solution for Stalling {
+        const DAY: u8 = 1;
+        const TITLE: &'static str = "Stalling";
+        type Input = u64;
+
+        fn parse(input: &str) -> Result<u64, ParseError> {
+            match input.trim() {
+                "broken" => panic!("The input is broken"),
+                "slow" => thread::sleep(Duration::from_secs(1)),
+                _ => {}
+            }
+            parse::number(input, input.trim())
+        }
+
+        fn part1(input: &u64) -> Answer {
+            Answer::from(*input)
+        }
+
+        fn part2(input: &u64) -> Answer {
+            Answer::from(*input * 2)
+        }
+    }
+
+    #[test]
+    fn guards_parsing() {
+        let puzzle = Puzzle::of::<Stalling>();
+        let params = Params::defaults(puzzle.params);
+        let timeout = Duration::from_millis(100);
+        let outcome = |input: &str, timeout| {
+            let report = solve_input(&puzzle, input, &Part::BOTH, &params, timeout);
+            assert!(report.failures.is_empty());
+            report.outcome
+        };
+
+        assert!(matches!(
+            outcome("broken\n", None),
+            Outcome::ParseAborted(Failure::Panicked(message)) if message == "The input is broken"
+        ));
+        assert!(matches!(
+            outcome("broken\n", Some(timeout)),
+            Outcome::ParseAborted(Failure::Panicked(_))
+        ));
+        assert!(matches!(
+            outcome("slow\n", Some(timeout)),
+            Outcome::ParseAborted(Failure::TimedOut(t)) if t == timeout
+        ));
+        assert!(matches!(
+            outcome("ten\n", Some(timeout)),
+            Outcome::ParseFailed(_)
+        ));
+        let Outcome::Solved(run) = outcome("10\n", Some(timeout)) else {
+            panic!("The input was not solved");
+        };
+        assert_eq!(2, run.parts.len());
+    }
+
+    #[test]
+    fn parses_days() {
+        assert_eq!(Ok(Days::All), "all".parse());
+        assert_eq!(Ok(Days::Year(2022)), "2022".parse());
+        assert_eq!(Ok(Days::Range(3, 7)), "3-7".parse());
+        assert_eq!(Ok(Days::Range(12, 12)), "12".parse());
+        assert!("7-3".parse::<Days>().is_err());
+        assert!("26".parse::<Days>().is_err());
+        assert_eq!(Some(1..=25), Days::Year(2022).resolve(2022));
+        assert_eq!(None, Days::Year(2025).resolve(2022));
+    }
+}
@@ -0,0 +1,228 @@
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+
+use crate::examples::EXAMPLES_FILE;
+use crate::input_utils::{self, RESOURCES_DIR};
+
+/// Registry of the solutions of a year crate, relative to the crate
+pub const PUZZLES_FILE: &str = "src/puzzles.rs";
+/// Directory of the solution modules, relative to the crate
+pub const PUZZLES_DIR: &str = "src/puzzles";
+
+/// Source of a new solution whose parts are still to be written
+pub fn module_code(day: u8, title: &str) -> String {
+    format!(
+        r#"use aoc_core::answer::Answer;
+use aoc_core::parse::ParseError;
+use aoc_core::solution::Solution;
+
+pub struct Day{day:0>2};
+
+impl Solution for Day{day:0>2} {{
+    const DAY: u8 = {day};
+    const TITLE: &'static str = {title:?};
+    type Input = Vec<String>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {{
+        Ok(input.lines().map(String::from).collect())
+    }}
+
+    fn part1(_input: &Self::Input) -> Answer {{
+        todo!("Solve part 1 of day {day}")
+    }}
+
+    fn part2(_input: &Self::Input) -> Answer {{
+        todo!("Solve part 2 of day {day}")
+    }}
+}}
+"#
+    )
+}
+
+/// Adds `mod day_XX;` and the solution to the registry, keeping both in order of the days
+pub fn register(registry: &str, day: u8) -> Result<String, String> {
+    let module = format!("day_{day:0>2}");
+    let mod_line = format!("mod {module};");
+    let entry = format!("{module}::Day{day:0>2},");
+    let mut lines: Vec<String> = registry.lines().map(String::from).collect();
+    if lines.iter().any(|line| line.trim() == mod_line) {
+        return Err(format!("Day {day} is already registered in {PUZZLES_FILE}"));
+    }
+
+    let mods: Vec<usize> = (0..lines.len())
+        .filter(|&i| lines[i].starts_with("mod day_"))
+        .collect();
+    let at = mods
+        .iter()
+        .find(|&&i| lines[i].as_str() > mod_line.as_str())
+        .copied()
+        .or(mods.last().map(|&last| last + 1))
+        .unwrap_or(0);
+    lines.insert(at, mod_line);
+
+    let start = lines
+        .iter()
+        .position(|line| line.contains("puzzles!["))
+        .ok_or_else(|| format!("No `aoc_core::puzzles!` in {PUZZLES_FILE}"))?;
+    let end = (start..lines.len())
+        .find(|&i| lines[i].trim() == "]")
+        .ok_or_else(|| format!("The `aoc_core::puzzles!` in {PUZZLES_FILE} is not closed"))?;
+    let entries: Vec<usize> = (start..end)
+        .filter(|&i| lines[i].trim().starts_with("day_"))
+        .collect();
+    let indent = entries.first().map_or(String::from("        "), |&i| {
+        lines[i][..lines[i].len() - lines[i].trim_start().len()].to_string()
+    });
+    let at = entries
+        .iter()
+        .find(|&&i| lines[i].trim() > entry.as_str())
+        .copied()
+        .unwrap_or(end);
+    lines.insert(at, format!("{indent}{entry}"));
+
+    Ok(lines.join("\n") + "\n")
+}
+
+/// Entry of the examples file for the first example of the day, failing until it is filled in
+pub fn example_entry(day: u8) -> String {
+    format!(
+        "\n[[{day}]]\n# The answers given for the example in the puzzle description\npart1 = 0\n"
+    )
+}
+
+/// Creates the solution module of a day in the year crate at `root`, registers
+/// it and adds an example together with empty input files to fill in
+///
+/// Returns what was done, nothing is changed if the day exists already.
+pub fn new_day(root: &Path, day: u8, title: &str) -> Result<Vec<String>, String> {
+    let module = root.join(PUZZLES_DIR).join(format!("day_{day:0>2}.rs"));
+    if module.exists() {
+        return Err(format!("{} exists already", module.display()));
+    }
+    let registry_path = root.join(PUZZLES_FILE);
+    let registry = fs::read_to_string(&registry_path)
+        .map_err(|error| format!("Could not read {}: {error}", registry_path.display()))?;
+    let registry = register(&registry, day)?;
+
+    let resources = root.join(RESOURCES_DIR);
+    let test_input = resources.join(input_utils::test_input_file_name(day.into()));
+    let input = resources.join(input_utils::input_file_name(day.into()));
+    let examples = resources.join(EXAMPLES_FILE);
+
+    write(&module, &module_code(day, title))?;
+    write(&registry_path, &registry)?;
+    let mut done = vec![
+        format!("Created {}", module.display()),
+        format!("Registered day {day} in {}", registry_path.display()),
+    ];
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&examples)
+        .and_then(|mut file| file.write_all(example_entry(day).as_bytes()))
+        .map_err(|error| format!("Could not write {}: {error}", examples.display()))?;
+    done.push(format!(
+        "Added an example to {}, fill in its answers",
+        examples.display()
+    ));
+    for (path, purpose) in [
+        (test_input, "paste the example input there"),
+        (input, "fetch the input or paste it there"),
+    ] {
+        if !path.exists() {
+            write(&path, "")?;
+            done.push(format!("Created {}, {purpose}", path.display()));
+        }
+    }
+    Ok(done)
+}
+
+fn write(path: &Path, contents: &str) -> Result<(), String> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)
+            .map_err(|error| format!("Could not create {}: {error}", dir.display()))?;
+    }
+    fs::write(path, contents)
+        .map_err(|error| format!("Could not write {}: {error}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::examples::Examples;
+
+    static REGISTRY: &str = "\
+mod day_01;
+mod day_11;
+
+use aoc_core::solution::Puzzles;
+
+pub fn get_all_puzzles() -> Puzzles {
+    aoc_core::puzzles![
+        2025;
+        day_01::Day01,
+        day_11::Day11,
+    ]
+}
+";
+
+    #[test]
+    fn registers_days_in_order() {
+        let registry = register(REGISTRY, 9).unwrap();
+        assert!(registry.starts_with("mod day_01;\nmod day_09;\nmod day_11;\n"));
+        assert!(registry.contains(
+            "        day_01::Day01,\n        day_09::Day09,\n        day_11::Day11,\n    ]"
+        ));
+
+        let registry = register(&registry, 12).unwrap();
+        assert!(registry.contains("mod day_11;\nmod day_12;\n\nuse"));
+        assert!(registry.contains("day_11::Day11,\n        day_12::Day12,\n    ]"));
+
+        assert!(register(&registry, 9).is_err());
+    }
+
+    #[test]
+    fn registers_the_first_day() {
+        let registry = "use aoc_core::solution::Puzzles;\n\npub fn get_all_puzzles() -> Puzzles {\n    aoc_core::puzzles![\n        2026;\n    ]\n}\n";
+        let registry = register(registry, 1).unwrap();
+        assert!(registry.starts_with("mod day_01;\nuse"));
+        assert!(registry.contains("        2026;\n        day_01::Day01,\n    ]"));
+    }
+
+    #[test]
+    fn creates_a_day() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join("resources")).unwrap();
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::write(root.join(PUZZLES_FILE), REGISTRY).unwrap();
+        fs::write(root.join("resources/examples.toml"), "[[1]]\npart1 = 3\n").unwrap();
+
+        let done = new_day(root, 9, "Movie Theater").unwrap();
+        assert_eq!(5, done.len());
+        let module = fs::read_to_string(root.join("src/puzzles/day_09.rs")).unwrap();
+        assert!(module.contains("pub struct Day09;"));
+        assert!(module.contains("const TITLE: &'static str = \"Movie Theater\";"));
+        assert!(
+            fs::read_to_string(root.join(PUZZLES_FILE))
+                .unwrap()
+                .contains("day_09::Day09,")
+        );
+        assert_eq!(
+            "",
+            fs::read_to_string(root.join("resources/day_09_input_test.txt")).unwrap()
+        );
+        assert_eq!(
+            "",
+            fs::read_to_string(root.join("resources/day_09_input.txt")).unwrap()
+        );
+
+        let examples =
+            Examples::parse(&fs::read_to_string(root.join("resources/examples.toml")).unwrap())
+                .unwrap();
+        assert_eq!("day_09_input_test.txt", examples.get(9, 1).unwrap().input);
+
+        assert!(new_day(root, 9, "Movie Theater").is_err());
+    }
+}
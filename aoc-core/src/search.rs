@@ -0,0 +1,351 @@
+use std::cmp::Ordering;
+use std::collections::hash_map::Entry;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+/// Number type of the costs of edges, `Default` being no cost
+pub trait Cost: Copy + Ord + Add<Output = Self> + Default {}
+
+impl<T: Copy + Ord + Add<Output = T> + Default> Cost for T {}
+
+/// What a search found: the cheapest cost of every node it reached, the node
+/// each was reached from and the goal it stopped at
+#[derive(Clone, Debug)]
+pub struct Search<N, C> {
+    costs: HashMap<N, C>,
+    parents: HashMap<N, N>,
+    goal: Option<N>,
+}
+
+impl<N: Clone + Eq + Hash, C: Copy> Search<N, C> {
+    /// The first goal reached, the cheapest one
+    pub fn goal(&self) -> Option<&N> {
+        self.goal.as_ref()
+    }
+
+    /// Cost of the goal
+    pub fn goal_cost(&self) -> Option<C> {
+        self.cost(self.goal.as_ref()?)
+    }
+
+    /// Cost of a reached node, the searches that stopped at a goal may not have
+    /// settled nodes further away than it
+    pub fn cost(&self, node: &N) -> Option<C> {
+        self.costs.get(node).copied()
+    }
+
+    pub fn costs(&self) -> &HashMap<N, C> {
+        &self.costs
+    }
+
+    /// Nodes from the start that was used up to the node, both included
+    pub fn path_to(&self, node: &N) -> Option<Vec<N>> {
+        if !self.costs.contains_key(node) {
+            return None;
+        }
+        let mut path = vec![node.clone()];
+        while let Some(parent) = self.parents.get(path.last()?) {
+            path.push(parent.clone());
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// Path to the goal, see [`Search::path_to`]
+    pub fn goal_path(&self) -> Option<Vec<N>> {
+        self.path_to(self.goal.as_ref()?)
+    }
+}
+
+/// Breadth first search from all of `starts` at once, every step costing one,
+/// until a node is a goal
+///
+/// With `|_| false` as `is_goal` every reachable node is visited. To search
+/// backwards, e.g. from the end to any of several starts, give the nodes that
+/// lead to a node as its neighbours.
+pub fn bfs<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut neighbors: impl FnMut(&N) -> I,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Search<N, usize>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut search = Search {
+        costs: HashMap::new(),
+        parents: HashMap::new(),
+        goal: None,
+    };
+    let mut queue = VecDeque::new();
+    for start in starts {
+        if let Entry::Vacant(entry) = search.costs.entry(start.clone()) {
+            entry.insert(0);
+            queue.push_back(start);
+        }
+    }
+    while let Some(node) = queue.pop_front() {
+        if is_goal(&node) {
+            search.goal = Some(node);
+            break;
+        }
+        let cost = search.costs[&node] + 1;
+        for next in neighbors(&node) {
+            if let Entry::Vacant(entry) = search.costs.entry(next.clone()) {
+                entry.insert(cost);
+                search.parents.insert(next.clone(), node.clone());
+                queue.push_back(next);
+            }
+        }
+    }
+    search
+}
+
+/// Cheapest paths from all of `starts` at once over edges of different costs,
+/// until a node is a goal
+///
+/// `neighbors` gives the nodes the node leads to with the costs of the edges.
+pub fn dijkstra<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    neighbors: impl FnMut(&N) -> I,
+    is_goal: impl FnMut(&N) -> bool,
+) -> Search<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Cost,
+    I: IntoIterator<Item = (N, C)>,
+{
+    astar(starts, neighbors, |_| C::default(), is_goal)
+}
+
+/// [`dijkstra`] looking at the nodes closer to a goal first
+///
+/// `heuristic` estimates the cost from a node to the nearest goal. It must
+/// never estimate more than the real cost, or the goal found may not be the
+/// cheapest.
+pub fn astar<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut neighbors: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Search<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Cost,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut search = Search {
+        costs: HashMap::new(),
+        parents: HashMap::new(),
+        goal: None,
+    };
+    let mut queue = BinaryHeap::new();
+    for start in starts {
+        if let Entry::Vacant(entry) = search.costs.entry(start.clone()) {
+            entry.insert(C::default());
+            queue.push(Queued {
+                priority: heuristic(&start),
+                cost: C::default(),
+                node: start,
+            });
+        }
+    }
+    while let Some(Queued { cost, node, .. }) = queue.pop() {
+        if search.costs[&node] < cost {
+            continue;
+        }
+        if is_goal(&node) {
+            search.goal = Some(node);
+            break;
+        }
+        for (next, step) in neighbors(&node) {
+            let next_cost = cost + step;
+            match search.costs.entry(next.clone()) {
+                Entry::Occupied(entry) if *entry.get() <= next_cost => continue,
+                Entry::Occupied(mut entry) => {
+                    entry.insert(next_cost);
+                }
+                Entry::Vacant(entry) => {
+                    entry.insert(next_cost);
+                }
+            }
+            search.parents.insert(next.clone(), node.clone());
+            queue.push(Queued {
+                priority: next_cost + heuristic(&next),
+                cost: next_cost,
+                node: next,
+            });
+        }
+    }
+    search
+}
+
+/// A node waiting in the queue of [`astar`], the lowest priority first
+struct Queued<N, C> {
+    priority: C,
+    cost: C,
+    node: N,
+}
+
+impl<N, C: Ord> PartialEq for Queued<N, C> {
+    fn eq(&self, other: &Queued<N, C>) -> bool {
+        self.priority == other.priority
+    }
+}
+
+impl<N, C: Ord> Eq for Queued<N, C> {}
+
+impl<N, C: Ord> PartialOrd for Queued<N, C> {
+    fn partial_cmp(&self, other: &Queued<N, C>) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<N, C: Ord> Ord for Queued<N, C> {
+    fn cmp(&self, other: &Queued<N, C>) -> Ordering {
+        other.priority.cmp(&self.priority)
+    }
+}
+
+/// The edges of a graph turned around, every node listing the nodes leading to it
+/// with the costs of the edges, for searching backwards
+pub fn reverse_edges<N, C, I>(
+    nodes: impl IntoIterator<Item = N>,
+    mut neighbors: impl FnMut(&N) -> I,
+) -> HashMap<N, Vec<(N, C)>>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut reversed: HashMap<N, Vec<(N, C)>> = HashMap::new();
+    for node in nodes {
+        for (next, cost) in neighbors(&node) {
+            reversed.entry(next).or_default().push((node.clone(), cost));
+        }
+    }
+    reversed
+}
+
+/// Cheapest costs between all pairs of the nodes `0..count`, `None` where
+/// there is no path, from the edges `(from, to, cost)`
+///
+/// The cost from a node to itself is no cost.
+pub fn floyd_warshall<C: Cost>(
+    count: usize,
+    edges: impl IntoIterator<Item = (usize, usize, C)>,
+) -> Vec<Vec<Option<C>>> {
+    let mut costs = vec![vec![None; count]; count];
+    for (node, row) in costs.iter_mut().enumerate() {
+        row[node] = Some(C::default());
+    }
+    for (from, to, cost) in edges {
+        let known = &mut costs[from][to];
+        if known.is_none_or(|known| cost < known) {
+            *known = Some(cost);
+        }
+    }
+    for via in 0..count {
+        let from_via = costs[via].clone();
+        for row in &mut costs {
+            let Some(to_via) = row[via] else {
+                continue;
+            };
+            for (known, from_via) in row.iter_mut().zip(&from_via) {
+                if let Some(from_via) = from_via {
+                    let cost = to_via + *from_via;
+                    if known.is_none_or(|known| cost < known) {
+                        *known = Some(cost);
+                    }
+                }
+            }
+        }
+    }
+    costs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 0 -> 1 -> 2 -> 3 and 0 -> 4 -> 3, with 5 not reachable
+    fn graph(node: &u32) -> Vec<(u32, u32)> {
+        match node {
+            0 => vec![(1, 1), (4, 5)],
+            1 => vec![(2, 1)],
+            2 => vec![(3, 1)],
+            4 => vec![(3, 1)],
+            5 => vec![(0, 1)],
+            _ => vec![],
+        }
+    }
+
+    fn unweighted(node: &u32) -> Vec<u32> {
+        graph(node).into_iter().map(|(next, _)| next).collect()
+    }
+
+    #[test]
+    fn searches_breadth_first() {
+        let search = bfs([0], unweighted, |&node| node == 3);
+        assert_eq!(Some(&3), search.goal());
+        assert_eq!(Some(2), search.goal_cost());
+        assert_eq!(Some(vec![0, 4, 3]), search.goal_path());
+
+        let everything = bfs([0], unweighted, |_| false);
+        assert_eq!(None, everything.goal());
+        assert_eq!(5, everything.costs().len());
+        assert_eq!(None, everything.cost(&5));
+        assert_eq!(None, everything.path_to(&5));
+
+        let from_both = bfs([2, 4], unweighted, |_| false);
+        assert_eq!(Some(1), from_both.cost(&3));
+        assert_eq!(Some(vec![2]), from_both.path_to(&2));
+    }
+
+    #[test]
+    fn finds_the_cheapest_paths() {
+        let search = dijkstra([0], graph, |&node| node == 3);
+        assert_eq!(Some(3), search.goal_cost());
+        assert_eq!(Some(vec![0, 1, 2, 3]), search.goal_path());
+
+        let estimated = astar([0], graph, |&node| 3 - node.min(3), |&node| node == 3);
+        assert_eq!(Some(3), estimated.goal_cost());
+        assert_eq!(search.goal_path(), estimated.goal_path());
+
+        let everything = dijkstra([5], graph, |_| false);
+        assert_eq!(Some(6), everything.cost(&4));
+        assert_eq!(Some(4), everything.cost(&3));
+    }
+
+    #[test]
+    fn searches_backwards() {
+        let reversed = reverse_edges(0..6, graph);
+        let search = dijkstra(
+            [3],
+            |node| reversed.get(node).cloned().unwrap_or_default(),
+            |&node| node == 0,
+        );
+        assert_eq!(Some(3), search.goal_cost());
+        assert_eq!(Some(vec![3, 2, 1, 0]), search.goal_path());
+    }
+
+    #[test]
+    fn finds_all_pairs_costs() {
+        let edges = (0..6).flat_map(|from| {
+            graph(&from)
+                .into_iter()
+                .map(move |(to, cost)| (from as usize, to as usize, cost))
+        });
+        let costs = floyd_warshall(6, edges);
+        assert_eq!(Some(3), costs[0][3]);
+        assert_eq!(Some(4), costs[5][3]);
+        assert_eq!(Some(0), costs[4][4]);
+        assert_eq!(None, costs[3][0]);
+        for (from, row) in costs.iter().enumerate() {
+            let search = dijkstra([from as u32], graph, |_| false);
+            for (to, &cost) in row.iter().enumerate() {
+                assert_eq!(search.cost(&(to as u32)), cost);
+            }
+        }
+    }
+}
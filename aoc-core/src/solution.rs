@@ -0,0 +1,430 @@
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::answer::Answer;
+use crate::input_utils::RESOURCES_DIR;
+use crate::ocr;
+use crate::params::{Param, Params};
+use crate::parse::ParseError;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Part {
+    First,
+    Second,
+}
+
+impl Part {
+    pub const BOTH: [Part; 2] = [Part::First, Part::Second];
+
+    pub fn from_number(number: u8) -> Option<Part> {
+        match number {
+            1 => Some(Part::First),
+            2 => Some(Part::Second),
+            _ => None,
+        }
+    }
+
+    pub fn number(self) -> u8 {
+        match self {
+            Part::First => 1,
+            Part::Second => 2,
+        }
+    }
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.number())
+    }
+}
+
+/// Optional information about a solution that does not influence solving
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Metadata {
+    /// Free form notes, e.g. the approach used or known limitations
+    pub notes: Option<&'static str>,
+    /// Short labels like "slow" or "simulation"
+    pub tags: &'static [&'static str],
+}
+
+/// A solution for both parts of a single day
+pub trait Solution {
+    /// Day of the advent calendar, starting from 1
+    const DAY: u8;
+    /// Title of the puzzle as shown on the website
+    const TITLE: &'static str;
+    const METADATA: Metadata = Metadata {
+        notes: None,
+        tags: &[],
+    };
+    /// Numbers the solution can be tuned with, see [`Param`]
+    const PARAMS: &'static [Param] = &[];
+
+    /// Representation of the puzzle input both parts are solved from, shared
+    /// with the threads parts may be solved on
+    type Input: Send + Sync + 'static;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError>;
+
+    fn part1(input: &Self::Input) -> Answer;
+
+    fn part2(input: &Self::Input) -> Answer;
+
+    /// Solves the first part with the values of [`Solution::PARAMS`], overridden or not
+    ///
+    /// Only solutions that declare params need to override this, their `part1`
+    /// solves with the defaults.
+    fn part1_with(input: &Self::Input, _params: &Params) -> Answer {
+        Self::part1(input)
+    }
+
+    /// Solves the second part with the values of [`Solution::PARAMS`], overridden or not
+    fn part2_with(input: &Self::Input, _params: &Params) -> Answer {
+        Self::part2(input)
+    }
+
+    fn solve_first(input: &str) -> Result<Answer, ParseError> {
+        let parsed = Self::parse(input).map_err(|e| e.in_day(Self::DAY))?;
+        Ok(Self::part1(&parsed))
+    }
+
+    fn solve_second(input: &str) -> Result<Answer, ParseError> {
+        let parsed = Self::parse(input).map_err(|e| e.in_day(Self::DAY))?;
+        Ok(Self::part2(&parsed))
+    }
+}
+
+/// Answer of a single part together with the time it took to solve it
+#[derive(Debug)]
+pub struct PartRun {
+    pub part: Part,
+    pub answer: Answer,
+    pub duration: Duration,
+}
+
+/// Outcome of solving one or more parts from a single parse of the input
+#[derive(Debug)]
+pub struct Run {
+    pub parse_duration: Duration,
+    pub parts: Vec<PartRun>,
+}
+
+/// Solving of a single part from the parsed input, handed out by [`Prepared::task`]
+pub type PartTask = Box<dyn FnOnce() -> PartRun + Send>;
+
+/// The parsed input of a puzzle together with the params to solve it with,
+/// see [`Puzzle::prepare`]
+pub struct Prepared {
+    pub parse_duration: Duration,
+    task: Box<dyn Fn(Part) -> PartTask + Send + Sync>,
+}
+
+impl Prepared {
+    /// The solving of the part, to be run however the caller decides, e.g.
+    /// catching panics or on a thread of its own
+    pub fn task(&self, part: Part) -> PartTask {
+        (self.task)(part)
+    }
+}
+
+fn prepare<S: Solution>(input: &str, params: &Params) -> Result<Prepared, ParseError> {
+    let start = Instant::now();
+    let parsed = Arc::new(S::parse(input).map_err(|e| e.in_day(S::DAY))?);
+    let parse_duration = start.elapsed();
+
+    let params = params.clone();
+    Ok(Prepared {
+        parse_duration,
+        task: Box::new(move |part| {
+            let (parsed, params) = (Arc::clone(&parsed), params.clone());
+            Box::new(move || {
+                let start = Instant::now();
+                let answer = match part {
+                    Part::First => S::part1_with(&parsed, &params),
+                    Part::Second => S::part2_with(&parsed, &params),
+                };
+                let duration = start.elapsed();
+                PartRun {
+                    part,
+                    answer: ocr::recognize_answer(answer),
+                    duration,
+                }
+            })
+        }),
+    })
+}
+
+/// Type erased [`Solution`], so solutions of different days can be kept together
+#[derive(Clone, Copy)]
+pub struct Puzzle {
+    pub day: u8,
+    pub title: &'static str,
+    pub metadata: Metadata,
+    pub params: &'static [Param],
+    prepare: fn(&str, &Params) -> Result<Prepared, ParseError>,
+}
+
+impl Puzzle {
+    pub fn of<S: Solution>() -> Puzzle {
+        Puzzle {
+            day: S::DAY,
+            title: S::TITLE,
+            metadata: S::METADATA,
+            params: S::PARAMS,
+            prepare: prepare::<S>,
+        }
+    }
+
+    /// Parses the input once and solves the given parts from it
+    pub fn run(&self, input: &str, parts: &[Part]) -> Result<Run, ParseError> {
+        self.run_with(input, parts, &Params::defaults(self.params))
+    }
+
+    /// Like [`Puzzle::run`], but solved with `params` resolved from [`Puzzle::params`]
+    pub fn run_with(
+        &self,
+        input: &str,
+        parts: &[Part],
+        params: &Params,
+    ) -> Result<Run, ParseError> {
+        let prepared = self.prepare(input, params)?;
+        Ok(Run {
+            parse_duration: prepared.parse_duration,
+            parts: parts.iter().map(|&part| prepared.task(part)()).collect(),
+        })
+    }
+
+    /// Parses the input once, the parts are solved from it by running the
+    /// tasks of the [`Prepared`] input
+    pub fn prepare(&self, input: &str, params: &Params) -> Result<Prepared, ParseError> {
+        (self.prepare)(input, params)
+    }
+
+    pub fn solve(&self, input: &str, part: Part) -> Result<Answer, ParseError> {
+        let mut run = self.run(input, &[part])?;
+        Ok(run.parts.remove(0).answer)
+    }
+}
+
+/// All implemented puzzles of a year, ordered by day
+pub struct Puzzles {
+    year: u16,
+    puzzles: Vec<Puzzle>,
+    resources_dir: Option<&'static str>,
+}
+
+impl Puzzles {
+    pub fn new(year: u16, mut puzzles: Vec<Puzzle>) -> Puzzles {
+        puzzles.sort_by_key(|p| p.day);
+        for pair in puzzles.windows(2) {
+            if pair[0].day == pair[1].day {
+                panic!("Day {} is registered more than once", pair[0].day);
+            }
+        }
+        Puzzles {
+            year,
+            puzzles,
+            resources_dir: None,
+        }
+    }
+
+    /// Sets the resources directory of the crate, inputs are read from there
+    /// when they are not found elsewhere
+    pub fn with_resources_dir(self, dir: &'static str) -> Puzzles {
+        Puzzles {
+            resources_dir: Some(dir),
+            ..self
+        }
+    }
+
+    pub fn year(&self) -> u16 {
+        self.year
+    }
+
+    pub fn resources_dir(&self) -> Option<&Path> {
+        self.resources_dir.map(Path::new)
+    }
+
+    /// Path of a file in the resources directory, relative to the working
+    /// directory when no resources directory is set
+    pub fn resource(&self, file_name: &str) -> PathBuf {
+        self.resources_dir()
+            .unwrap_or(Path::new(RESOURCES_DIR))
+            .join(file_name)
+    }
+
+    /// Root of the year crate, the parent of the resources directory, or the
+    /// working directory when no resources directory is set
+    pub fn crate_dir(&self) -> &Path {
+        self.resources_dir()
+            .and_then(Path::parent)
+            .unwrap_or(Path::new("."))
+    }
+
+    pub fn get(&self, day: u8) -> Option<&Puzzle> {
+        self.puzzles.iter().find(|p| p.day == day)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Puzzle> {
+        self.puzzles.iter()
+    }
+
+    pub fn days(&self) -> Vec<u8> {
+        self.puzzles.iter().map(|p| p.day).collect()
+    }
+}
+
+/// Collects [`Solution`] implementations of a year into [`Puzzles`]
+///
+/// ```ignore
+/// pub fn get_all_puzzles() -> Puzzles {
+///     aoc_core::puzzles![2022; day_01::Day01, day_02::Day02]
+/// }
+/// ```
+#[macro_export]
+macro_rules! puzzles {
+    ($year:expr; $($solution:ty),* $(,)?) => {
+        $crate::solution::Puzzles::new($year, vec![
+            $($crate::solution::Puzzle::of::<$solution>()),*
+        ])
+        .with_resources_dir(concat!(env!("CARGO_MANIFEST_DIR"), "/resources"))
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct First;
+
+    impl Solution for First {
+        const DAY: u8 = 3;
+        const TITLE: &'static str = "First";
+        type Input = usize;
+
+        fn parse(input: &str) -> Result<usize, ParseError> {
+            Ok(input.len())
+        }
+
+        fn part1(input: &usize) -> Answer {
+            Answer::from(*input)
+        }
+
+        fn part2(input: &usize) -> Answer {
+            Answer::from(*input * 2)
+        }
+    }
+
+    struct Second;
+
+    impl Solution for Second {
+        const DAY: u8 = 1;
+        const TITLE: &'static str = "Second";
+        type Input = String;
+
+        fn parse(input: &str) -> Result<String, ParseError> {
+            match input.find(|c: char| !c.is_alphabetic()) {
+                Some(i) => Err(ParseError::at(input, &input[i..=i], "Expected a letter")),
+                None => Ok(input.to_uppercase()),
+            }
+        }
+
+        fn part1(input: &String) -> Answer {
+            Answer::Text(input.clone())
+        }
+
+        fn part2(input: &String) -> Answer {
+            Answer::Text(input.chars().rev().collect())
+        }
+    }
+
+    #[test]
+    fn orders_puzzles_by_day() {
+        let puzzles = crate::puzzles![2022; First, Second];
+        assert_eq!(2022, puzzles.year());
+        assert_eq!(vec![1, 3], puzzles.days());
+        assert!(puzzles.get(2).is_none());
+    }
+
+    #[test]
+    fn solves_registered_puzzle() {
+        let puzzles = crate::puzzles![2022; First, Second];
+        let second = puzzles.get(1).unwrap();
+        assert_eq!("Second", second.title);
+        assert_eq!(
+            Ok(Answer::Text(String::from("CBA"))),
+            second.solve("abc", Part::Second)
+        );
+        assert_eq!(
+            Ok(Answer::from(8)),
+            puzzles.get(3).unwrap().solve("abcd", Part::Second)
+        );
+    }
+
+    #[test]
+    fn runs_both_parts_from_one_parse() {
+        let run = Puzzle::of::<Second>().run("xyz", &Part::BOTH).unwrap();
+        let answers: Vec<(Part, Answer)> =
+            run.parts.into_iter().map(|p| (p.part, p.answer)).collect();
+        assert_eq!(
+            vec![
+                (Part::First, Answer::Text(String::from("XYZ"))),
+                (Part::Second, Answer::Text(String::from("ZYX"))),
+            ],
+            answers
+        );
+    }
+
+    struct Tuned;
+
+    impl Solution for Tuned {
+        const DAY: u8 = 15;
+        const TITLE: &'static str = "Tuned";
+        const PARAMS: &'static [Param] =
+            &[Param::new::<usize>("factor", 10, "Multiplies the length")];
+        type Input = usize;
+
+        fn parse(input: &str) -> Result<usize, ParseError> {
+            Ok(input.len())
+        }
+
+        fn part1(input: &usize) -> Answer {
+            Self::part1_with(input, &Params::defaults(Self::PARAMS))
+        }
+
+        fn part2(input: &usize) -> Answer {
+            Answer::from(*input)
+        }
+
+        fn part1_with(input: &usize, params: &Params) -> Answer {
+            Answer::from(*input * params.get::<usize>("factor"))
+        }
+    }
+
+    #[test]
+    fn solves_with_params() {
+        let puzzle = Puzzle::of::<Tuned>();
+        assert_eq!(Ok(Answer::from(30)), puzzle.solve("abc", Part::First));
+
+        let mut overrides = Params::default();
+        overrides.set("factor", 2);
+        let params = Params::resolve(puzzle.params, &overrides).unwrap();
+        let run = puzzle.run_with("abc", &Part::BOTH, &params).unwrap();
+        let answers: Vec<Answer> = run.parts.into_iter().map(|p| p.answer).collect();
+        assert_eq!(vec![Answer::from(6), Answer::from(3)], answers);
+    }
+
+    #[test]
+    fn reports_parse_error_with_day() {
+        let error = Second::solve_first("ab\nc").unwrap_err();
+        assert_eq!((1, 1, 3), (error.day, error.line, error.column));
+    }
+
+    #[test]
+    #[should_panic(expected = "Day 3 is registered more than once")]
+    fn rejects_duplicate_days() {
+        crate::puzzles![2022; First, First];
+    }
+}
@@ -0,0 +1,519 @@
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::PathBuf;
+
+use num_bigint::BigInt;
+
+use crate::fetch::{BASE_URL, FetchError, Http};
+use crate::solution::Part;
+
+/// File in the input directory every submitted answer is recorded in
+pub const LEDGER_FILE: &str = "submissions.toml";
+
+/// What adventofcode.com made of a submitted answer
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Response {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong, without a hint in which direction
+    Wrong,
+    /// Answers are only accepted again after a waiting time
+    TooSoon,
+    /// The part was solved already, or the first part is still open
+    WrongLevel,
+    Unrecognized,
+}
+
+impl Response {
+    /// Reads the verdict from the page the website answers with
+    pub fn parse(html: &str) -> Response {
+        let text = article_text(html);
+        if text.contains("That's the right answer") {
+            Response::Correct
+        } else if text.contains("That's not the right answer") {
+            if text.contains("too high") {
+                Response::TooHigh
+            } else if text.contains("too low") {
+                Response::TooLow
+            } else {
+                Response::Wrong
+            }
+        } else if text.contains("You gave an answer too recently") {
+            Response::TooSoon
+        } else if text.contains("You don't seem to be solving the right level") {
+            Response::WrongLevel
+        } else {
+            Response::Unrecognized
+        }
+    }
+
+    /// Whether the answer was judged, only those responses are recorded in the [`Ledger`]
+    pub fn is_verdict(self) -> bool {
+        matches!(
+            self,
+            Response::Correct | Response::TooHigh | Response::TooLow | Response::Wrong
+        )
+    }
+
+    fn from_name(name: &str) -> Option<Response> {
+        [
+            Response::Correct,
+            Response::TooHigh,
+            Response::TooLow,
+            Response::Wrong,
+        ]
+        .into_iter()
+        .find(|response| response.to_string() == name)
+    }
+}
+
+impl fmt::Display for Response {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Response::Correct => write!(f, "correct"),
+            Response::TooHigh => write!(f, "too high"),
+            Response::TooLow => write!(f, "too low"),
+            Response::Wrong => write!(f, "wrong"),
+            Response::TooSoon => write!(f, "too soon"),
+            Response::WrongLevel => write!(f, "wrong level"),
+            Response::Unrecognized => write!(f, "unrecognized"),
+        }
+    }
+}
+
+/// Text of the `<article>` holding the message of a page, without markup
+pub fn article_text(html: &str) -> String {
+    let article = html
+        .split_once("<article")
+        .and_then(|(_, rest)| rest.split_once('>'))
+        .map_or(html, |(_, rest)| {
+            rest.split_once("</article>")
+                .map_or(rest, |(article, _)| article)
+        });
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in article.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            _ if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// The response to a submitted answer together with the message shown on the website
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Reply {
+    pub response: Response,
+    pub message: String,
+}
+
+/// Posts answers to adventofcode.com
+pub struct Submitter<H: Http> {
+    http: H,
+    session: String,
+    base_url: String,
+}
+
+impl<H: Http> Submitter<H> {
+    pub fn new(http: H, session: impl Into<String>) -> Submitter<H> {
+        Submitter {
+            http,
+            session: session.into(),
+            base_url: String::from(BASE_URL),
+        }
+    }
+
+    pub fn with_base_url(self, base_url: impl Into<String>) -> Submitter<H> {
+        Submitter {
+            base_url: base_url.into(),
+            ..self
+        }
+    }
+
+    pub fn submit(
+        &self,
+        year: u16,
+        day: u8,
+        part: Part,
+        answer: &str,
+    ) -> Result<Reply, FetchError> {
+        let url = format!("{}/{year}/day/{day}/answer", self.base_url);
+        let level = part.to_string();
+        let html = self
+            .http
+            .post(
+                &url,
+                &self.session,
+                &[("level", &level), ("answer", answer)],
+            )
+            .map_err(|error| FetchError::Http { url, error })?;
+        Ok(Reply {
+            response: Response::parse(&html),
+            message: article_text(&html),
+        })
+    }
+}
+
+/// A judged answer
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Attempt {
+    pub day: u8,
+    pub part: Part,
+    pub answer: String,
+    pub response: Response,
+}
+
+/// What earlier attempts tell about an answer before it is submitted
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Known {
+    /// The answer was accepted before
+    Accepted,
+    /// Another answer was accepted before
+    OtherAccepted(String),
+    /// The answer was rejected before
+    Rejected(Response),
+    /// The answer is at most an answer that was too low
+    NotAbove(BigInt),
+    /// The answer is at least an answer that was too high
+    NotBelow(BigInt),
+}
+
+impl Known {
+    /// Whether submitting the answer would be a wasted attempt
+    pub fn is_wrong(&self) -> bool {
+        !matches!(self, Known::Accepted)
+    }
+}
+
+impl fmt::Display for Known {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Known::Accepted => write!(f, "Accepted before"),
+            Known::OtherAccepted(answer) => write!(f, "Known to be wrong, {answer} was accepted"),
+            Known::Rejected(response) => {
+                write!(f, "Known to be wrong, rejected before as {response}")
+            }
+            Known::NotAbove(low) => write!(f, "Known to be too low, the answer is above {low}"),
+            Known::NotBelow(high) => write!(f, "Known to be too high, the answer is below {high}"),
+        }
+    }
+}
+
+/// Every judged answer of a year, stored as TOML with an array of tables per day:
+///
+/// ```toml
+/// [[1]]
+/// part = 2
+/// answer = "206582"
+/// response = "too low"
+/// ```
+#[derive(Debug)]
+pub struct Ledger {
+    path: PathBuf,
+    attempts: Vec<Attempt>,
+}
+
+impl Ledger {
+    /// Reads the ledger at `path`, a missing file means nothing was submitted yet
+    pub fn load(path: impl Into<PathBuf>) -> Result<Ledger, String> {
+        let path = path.into();
+        let text = match fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(error) => return Err(format!("Could not read {}: {error}", path.display())),
+        };
+        let attempts = parse_attempts(&text)
+            .map_err(|error| format!("Invalid ledger {}: {error}", path.display()))?;
+        Ok(Ledger { path, attempts })
+    }
+
+    /// Attempts at a part in the order they were made
+    pub fn attempts(&self, day: u8, part: Part) -> impl Iterator<Item = &Attempt> {
+        self.attempts
+            .iter()
+            .filter(move |attempt| attempt.day == day && attempt.part == part)
+    }
+
+    /// The highest answer that was too low and the lowest one that was too high
+    pub fn bounds(&self, day: u8, part: Part) -> (Option<BigInt>, Option<BigInt>) {
+        let numbers = |response| {
+            self.attempts(day, part)
+                .filter(move |attempt| attempt.response == response)
+                .filter_map(|attempt| attempt.answer.parse::<BigInt>().ok())
+        };
+        (
+            numbers(Response::TooLow).max(),
+            numbers(Response::TooHigh).min(),
+        )
+    }
+
+    /// What the earlier attempts tell about `answer`, `None` if it may be right
+    pub fn check(&self, day: u8, part: Part, answer: &str) -> Option<Known> {
+        if let Some(accepted) = self
+            .attempts(day, part)
+            .find(|attempt| attempt.response == Response::Correct)
+        {
+            return Some(if accepted.answer == answer {
+                Known::Accepted
+            } else {
+                Known::OtherAccepted(accepted.answer.clone())
+            });
+        }
+        if let Some(rejected) = self
+            .attempts(day, part)
+            .find(|attempt| attempt.answer == answer)
+        {
+            return Some(Known::Rejected(rejected.response));
+        }
+
+        let number: BigInt = answer.parse().ok()?;
+        match self.bounds(day, part) {
+            (Some(low), _) if number <= low => Some(Known::NotAbove(low)),
+            (_, Some(high)) if number >= high => Some(Known::NotBelow(high)),
+            _ => None,
+        }
+    }
+
+    /// Appends a judged answer to the ledger file
+    pub fn record(&mut self, attempt: Attempt) -> Result<(), String> {
+        let entry = format!(
+            "\n[[{}]]\npart = {}\nanswer = {}\nresponse = \"{}\"\n",
+            attempt.day,
+            attempt.part,
+            toml::Value::from(attempt.answer.as_str()),
+            attempt.response
+        );
+        if let Some(dir) = self.path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            fs::create_dir_all(dir)
+                .map_err(|error| format!("Could not create {}: {error}", dir.display()))?;
+        }
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .and_then(|mut file| file.write_all(entry.as_bytes()))
+            .map_err(|error| format!("Could not write {}: {error}", self.path.display()))?;
+        self.attempts.push(attempt);
+        Ok(())
+    }
+}
+
+fn parse_attempts(text: &str) -> Result<Vec<Attempt>, String> {
+    let table: toml::Table = text.parse().map_err(|error| format!("{error}"))?;
+    let mut attempts = Vec::new();
+    for (day_key, entries) in table {
+        let day: u8 = day_key
+            .parse()
+            .map_err(|_| format!("Expected a day instead of `{day_key}`"))?;
+        let entries = entries
+            .as_array()
+            .ok_or_else(|| format!("Expected `[[{day}]]` tables for day {day}"))?;
+        for entry in entries {
+            let entry = entry
+                .as_table()
+                .ok_or_else(|| format!("Expected `[[{day}]]` tables for day {day}"))?;
+            let part = entry
+                .get("part")
+                .and_then(toml::Value::as_integer)
+                .and_then(|part| Part::from_number(u8::try_from(part).ok()?))
+                .ok_or_else(|| format!("Expected part 1 or 2 for an attempt of day {day}"))?;
+            let answer = entry
+                .get("answer")
+                .and_then(toml::Value::as_str)
+                .ok_or_else(|| format!("Expected an answer for an attempt of day {day}"))?;
+            let response = entry
+                .get("response")
+                .and_then(toml::Value::as_str)
+                .and_then(Response::from_name)
+                .ok_or_else(|| format!("Expected a response for an attempt of day {day}"))?;
+            attempts.push(Attempt {
+                day,
+                part,
+                answer: answer.to_string(),
+                response,
+            });
+        }
+    }
+    Ok(attempts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fetch::UreqHttp;
+    use crate::fetch::tests::serve_once;
+
+    fn attempt(part: Part, answer: &str, response: Response) -> Attempt {
+        Attempt {
+            day: 1,
+            part,
+            answer: answer.to_string(),
+            response,
+        }
+    }
+
+    #[test]
+    fn parses_responses() {
+        let page = |message| {
+            format!(
+                "<html><body><main>\n<article><p>{message}</p></article>\n</main></body></html>"
+            )
+        };
+        assert_eq!(
+            Response::Correct,
+            Response::parse(&page(
+                "That's the right answer!  You are <em>one gold star</em> closer."
+            ))
+        );
+        assert_eq!(
+            Response::TooHigh,
+            Response::parse(&page(
+                "That's not the right answer; your answer is too high."
+            ))
+        );
+        assert_eq!(
+            Response::TooLow,
+            Response::parse(&page(
+                "That's not the right answer; your answer is too low."
+            ))
+        );
+        assert_eq!(
+            Response::Wrong,
+            Response::parse(&page("That's not the right answer.  If you're stuck, ..."))
+        );
+        assert_eq!(
+            Response::TooSoon,
+            Response::parse(&page(
+                "You gave an answer too recently.  You have 35s left to wait."
+            ))
+        );
+        assert_eq!(
+            Response::WrongLevel,
+            Response::parse(&page(
+                "You don't seem to be solving the right level.  Did you already complete it?"
+            ))
+        );
+        assert_eq!(
+            "That's the right answer! You are one gold star closer.",
+            article_text(&page(
+                "That's the right answer!  You are <em>one gold star</em> closer."
+            ))
+        );
+    }
+
+    #[test]
+    fn refuses_known_wrong_answers() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut ledger = Ledger::load(dir.path().join(LEDGER_FILE)).unwrap();
+        ledger
+            .record(attempt(Part::First, "100", Response::TooLow))
+            .unwrap();
+        ledger
+            .record(attempt(Part::First, "500", Response::TooHigh))
+            .unwrap();
+        ledger
+            .record(attempt(Part::First, "300", Response::TooHigh))
+            .unwrap();
+        ledger
+            .record(attempt(Part::First, "200", Response::Wrong))
+            .unwrap();
+
+        assert_eq!(
+            (Some(BigInt::from(100)), Some(BigInt::from(300))),
+            ledger.bounds(1, Part::First)
+        );
+        assert_eq!(
+            Some(Known::NotAbove(BigInt::from(100))),
+            ledger.check(1, Part::First, "50")
+        );
+        assert_eq!(
+            Some(Known::NotBelow(BigInt::from(300))),
+            ledger.check(1, Part::First, "400")
+        );
+        assert_eq!(
+            Some(Known::Rejected(Response::Wrong)),
+            ledger.check(1, Part::First, "200")
+        );
+        assert_eq!(None, ledger.check(1, Part::First, "250"));
+        assert_eq!(None, ledger.check(1, Part::Second, "50"));
+
+        ledger
+            .record(attempt(Part::First, "250", Response::Correct))
+            .unwrap();
+        assert_eq!(Some(Known::Accepted), ledger.check(1, Part::First, "250"));
+        assert_eq!(
+            Some(Known::OtherAccepted(String::from("250"))),
+            ledger.check(1, Part::First, "251")
+        );
+    }
+
+    #[test]
+    fn keeps_attempts_in_the_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("inputs").join(LEDGER_FILE);
+        let mut ledger = Ledger::load(&path).unwrap();
+        ledger
+            .record(attempt(Part::Second, "EHZ\"", Response::Wrong))
+            .unwrap();
+        ledger
+            .record(Attempt {
+                day: 2,
+                ..attempt(Part::First, "7", Response::TooLow)
+            })
+            .unwrap();
+        ledger
+            .record(attempt(Part::Second, "EHPZ", Response::Correct))
+            .unwrap();
+
+        let reloaded = Ledger::load(&path).unwrap();
+        let answers: Vec<&str> = reloaded
+            .attempts(1, Part::Second)
+            .map(|attempt| attempt.answer.as_str())
+            .collect();
+        assert_eq!(vec!["EHZ\"", "EHPZ"], answers);
+        assert_eq!(
+            Some(Known::NotAbove(BigInt::from(7))),
+            reloaded.check(2, Part::First, "5")
+        );
+        assert!(
+            Ledger::load(dir.path().join("missing.toml"))
+                .unwrap()
+                .attempts(1, Part::First)
+                .next()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn posts_answers() {
+        let (address, requests) = serve_once(
+            "200 OK",
+            "<article><p>That's not the right answer; your answer is too low.</p></article>",
+        );
+        let submitter = Submitter::new(UreqHttp::default(), "abc").with_base_url(address);
+
+        let reply = submitter.submit(2022, 5, Part::Second, "CMZ").unwrap();
+        assert_eq!(Response::TooLow, reply.response);
+        assert_eq!(
+            "That's not the right answer; your answer is too low.",
+            reply.message
+        );
+        let request = requests.recv().unwrap();
+        assert!(
+            request
+                .to_lowercase()
+                .starts_with("post /2022/day/5/answer http/1.1\r\n")
+        );
+        assert!(
+            request
+                .to_lowercase()
+                .contains("\r\ncookie: session=abc\r\n")
+        );
+        assert!(request.ends_with("\r\n\r\nlevel=2&answer=CMZ"));
+    }
+}
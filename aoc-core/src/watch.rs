@@ -0,0 +1,269 @@
+use std::collections::BTreeMap;
+use std::env;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::time::Duration;
+
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use serde_json::Value;
+
+use crate::examples::{EXAMPLES_FILE, Examples};
+use crate::input_utils::{self, InputDirs, RESOURCES_DIR};
+use crate::scaffold::PUZZLES_DIR;
+use crate::solution::Part;
+
+/// How long to wait for further changes after one, editors often save a file in several steps
+pub const DEBOUNCE: Duration = Duration::from_millis(200);
+
+/// The answer or the error of every part that was solved
+pub type Answers = BTreeMap<Part, Result<String, String>>;
+
+/// The module of the day, the examples file and inputs of the examples of the
+/// day in the year crate at `root`, and the real input
+pub fn watched_files(root: &Path, day: u8, examples: &Examples, dirs: &InputDirs) -> Vec<PathBuf> {
+    let resources = root.join(RESOURCES_DIR);
+    let mut files = vec![
+        root.join(PUZZLES_DIR).join(format!("day_{day:0>2}.rs")),
+        resources.join(EXAMPLES_FILE),
+    ];
+    files.extend(
+        examples
+            .iter()
+            .filter(|example| example.day == day)
+            .map(|example| resources.join(&example.input)),
+    );
+    files.push(dirs.find_input(day.into()).unwrap_or_else(|| {
+        dirs.cache_dir()
+            .join(input_utils::input_file_name(day.into()))
+    }));
+    files.sort();
+    files.dedup();
+    files
+}
+
+/// Notices changes to a set of files
+pub struct FileWatcher {
+    /// Watching stops when it is dropped
+    _watcher: RecommendedWatcher,
+    events: Receiver<notify::Result<Event>>,
+    files: Vec<PathBuf>,
+}
+
+impl FileWatcher {
+    /// Watches the directories of the files, so files that editors replace or
+    /// that are created later are noticed too
+    ///
+    /// Files in directories that do not exist are left out.
+    pub fn new(files: &[PathBuf]) -> Result<FileWatcher, String> {
+        let (sender, events) = mpsc::channel();
+        let mut watcher = notify::recommended_watcher(sender)
+            .map_err(|error| format!("Could not watch files: {error}"))?;
+        let mut dirs = Vec::new();
+        let mut watched = Vec::new();
+        for file in files {
+            let (Some(dir), Some(name)) = (file.parent(), file.file_name()) else {
+                continue;
+            };
+            let dir = if dir.as_os_str().is_empty() {
+                Path::new(".")
+            } else {
+                dir
+            };
+            let Ok(dir) = dir.canonicalize() else {
+                continue;
+            };
+            if !dirs.contains(&dir) {
+                watcher
+                    .watch(&dir, RecursiveMode::NonRecursive)
+                    .map_err(|error| format!("Could not watch {}: {error}", dir.display()))?;
+                dirs.push(dir.clone());
+            }
+            watched.push(dir.join(name));
+        }
+        Ok(FileWatcher {
+            _watcher: watcher,
+            events,
+            files: watched,
+        })
+    }
+
+    /// Waits until some of the files changed and returns them, changes in
+    /// quick succession are returned together
+    pub fn wait(&self) -> Result<Vec<PathBuf>, String> {
+        let stopped = || String::from("Stopped watching files");
+        let mut changed: Vec<PathBuf> = Vec::new();
+        loop {
+            let event = if changed.is_empty() {
+                self.events.recv().map_err(|_| stopped())?
+            } else {
+                match self.events.recv_timeout(DEBOUNCE) {
+                    Ok(event) => event,
+                    Err(RecvTimeoutError::Timeout) => return Ok(changed),
+                    Err(RecvTimeoutError::Disconnected) => return Err(stopped()),
+                }
+            };
+            let event = event.map_err(|error| format!("Could not watch files: {error}"))?;
+            if !matches!(
+                event.kind,
+                EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_)
+            ) {
+                continue;
+            }
+            for path in event.paths {
+                if self.files.contains(&path) && !changed.contains(&path) {
+                    changed.push(path);
+                }
+            }
+        }
+    }
+}
+
+/// Runs the tests of the day, its examples among them, and when they pass
+/// solves the real input with a release build
+///
+/// Both are built with the cargo this was started from, in the year crate at
+/// `root`. Compiler errors and test failures are shown as cargo prints them.
+pub fn check_day(root: &Path, day: u8) -> Result<Answers, String> {
+    let cargo = env::var_os("CARGO").unwrap_or_else(|| "cargo".into());
+    let tests = Command::new(&cargo)
+        .current_dir(root)
+        .args(["test", "--quiet", "--lib", "--"])
+        .arg(format!("day_{day:0>2}"))
+        .stdout(Stdio::null())
+        .status()
+        .map_err(|error| format!("Could not run cargo: {error}"))?;
+    if !tests.success() {
+        return Err(format!(
+            "The tests of day {day} failed, the input is not solved"
+        ));
+    }
+
+    let output = Command::new(&cargo)
+        .current_dir(root)
+        .args(["run", "--release", "--quiet", "--"])
+        .arg(day.to_string())
+        .args(["--format", "json"])
+        .stderr(Stdio::inherit())
+        .output()
+        .map_err(|error| format!("Could not run cargo: {error}"))?;
+    parse_answers(&String::from_utf8_lossy(&output.stdout))
+        .map_err(|_| format!("Could not solve day {day}"))
+}
+
+/// Reads the answers from the results printed with `--format json`
+pub fn parse_answers(json: &str) -> Result<Answers, String> {
+    let value: Value =
+        serde_json::from_str(json.trim()).map_err(|error| format!("Invalid results: {error}"))?;
+    let results = value["results"]
+        .as_array()
+        .ok_or_else(|| String::from("Invalid results: no `results`"))?;
+    let mut answers = Answers::new();
+    for result in results {
+        let part = result["part"]
+            .as_u64()
+            .and_then(|part| Part::from_number(part.try_into().ok()?))
+            .ok_or_else(|| String::from("Invalid results: no `part`"))?;
+        let answer = match (result["answer"].as_str(), result["error"].as_str()) {
+            (Some(answer), _) => Ok(answer.to_string()),
+            (None, error) => Err(error.unwrap_or("no answer").to_string()),
+        };
+        answers.insert(part, answer);
+    }
+    Ok(answers)
+}
+
+/// A line per part with its latest answer and how it compares to the previous one
+pub fn describe(previous: &Answers, latest: &Answers) -> Vec<String> {
+    latest
+        .iter()
+        .map(|(part, answer)| match (answer, previous.get(part)) {
+            (Err(error), _) => format!("Part {part} failed: {error}"),
+            (Ok(answer), Some(Ok(before))) if answer == before => {
+                format!("Part {part}: {answer} (unchanged)")
+            }
+            (Ok(answer), Some(Ok(before))) if answer.contains('\n') || before.contains('\n') => {
+                format!("Part {part}: {answer} (changed)")
+            }
+            (Ok(answer), Some(Ok(before))) => format!("Part {part}: {answer} (was {before})"),
+            (Ok(answer), _) => format!("Part {part}: {answer}"),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::thread;
+
+    use super::*;
+
+    #[test]
+    fn watches_the_files_of_the_day() {
+        let examples = Examples::parse(
+            "[[9]]\npart1 = 50\n\n[[9]]\ninput = \"day_09_input_test_2.txt\"\npart2 = 24\n\n[[10]]\npart1 = 1",
+        )
+        .unwrap();
+        let dirs = InputDirs::new(vec![PathBuf::from("missing")]);
+        assert_eq!(
+            vec![
+                PathBuf::from("2025_rust/resources/day_09_input_test.txt"),
+                PathBuf::from("2025_rust/resources/day_09_input_test_2.txt"),
+                PathBuf::from("2025_rust/resources/examples.toml"),
+                PathBuf::from("2025_rust/src/puzzles/day_09.rs"),
+                PathBuf::from("missing/day_09_input.txt"),
+            ],
+            watched_files(Path::new("2025_rust"), 9, &examples, &dirs)
+        );
+    }
+
+    #[test]
+    fn notices_changed_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let input = dir.path().join("day_09_input.txt");
+        let watcher = FileWatcher::new(std::slice::from_ref(&input)).unwrap();
+
+        let other = dir.path().join("notes.txt");
+        let written = input.clone();
+        let writer = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(50));
+            fs::write(other, "unrelated").unwrap();
+            fs::write(written, "7,1\n").unwrap();
+        });
+        let changed = watcher.wait().unwrap();
+        writer.join().unwrap();
+        assert_eq!(vec![input.canonicalize().unwrap()], changed);
+    }
+
+    #[test]
+    fn compares_answers() {
+        let previous = parse_answers(
+            r#"{"year": 2025, "results": [
+                {"day": 9, "part": 1, "answer": "50", "error": null},
+                {"day": 9, "part": 2, "answer": "24", "error": null}]}"#,
+        )
+        .unwrap();
+        let latest = parse_answers(
+            r#"{"year": 2025, "results": [
+                {"day": 9, "part": 1, "answer": "50", "error": null},
+                {"day": 9, "part": 2, "answer": "25", "error": null}]}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            vec!["Part 1: 50 (unchanged)", "Part 2: 25 (was 24)"],
+            describe(&previous, &latest)
+        );
+
+        let failed = parse_answers(
+            r#"{"year": 2025, "results": [
+                {"day": 9, "part": 1, "answer": null, "error": "parse error"}]}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            vec!["Part 1 failed: parse error"],
+            describe(&latest, &failed)
+        );
+        assert_eq!(vec!["Part 1: 50"], describe(&failed, &previous)[..1]);
+        assert!(parse_answers("error").is_err());
+    }
+}